@@ -1,7 +1,7 @@
 //! Prints the average color of the screen
 
 use std::time::Instant;
-use undici::x11::{common::Vector2, display::Display, window::kill_window};
+use undici::x11::{display::Display, window::kill_window};
 
 fn main() {
     let starting_time = Instant::now();
@@ -15,25 +15,21 @@ fn main() {
 
     let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
 
-    for x in 0..screenshot.width {
-        for y in 0..screenshot.height {
-            let pixel = screenshot.get_pixel(Vector2::new(x as i32, y as i32));
-
-            r += pixel.r as u32;
-            g += pixel.g as u32;
-            b += pixel.b as u32;
-        }
+    for pixel in screenshot.pixels() {
+        r += pixel.r as u32;
+        g += pixel.g as u32;
+        b += pixel.b as u32;
     }
 
     let total_pixels = screenshot.width * screenshot.height;
-    let r = (r as u32 / total_pixels) as u8;
-    let g = (g as u32 / total_pixels) as u8;
-    let b = (b as u32 / total_pixels) as u8;
+    let r = (r / total_pixels) as u8;
+    let g = (g / total_pixels) as u8;
+    let b = (b / total_pixels) as u8;
 
     kill_window(&mut root_window);
 
     println!("Average screen color: #{:x}{:x}{:x}", r, g, b);
-    println!("");
+    println!();
 
     #[cfg(debug_assertions)]
     println!("You should run this with --release for it to be faster!!");
@@ -11,12 +11,19 @@ fn main() {
     // This sets the wm to open at the 90th display, so you can put a virtual screen using
     // the Xephyr linux command. Beware that this breaks if you have 90 monitors 😀
     // Example of command: `Xephyr -br -ac -noreset -screen 800x800 :90`
-    #[cfg(debug_assertions)] // this line makes it so it only does this in debug mode
-    std::env::set_var("DISPLAY", ":90");
-
-    let display = Display::new().expect("could not open display");
+    let display = if cfg!(debug_assertions) {
+        Display::open(":90") // this makes it so it only does this in debug mode
+    } else {
+        Display::new()
+    }
+    .expect("could not open display");
     let root_window = display.get_root_window();
 
+    // Without this, windows place themselves wherever they want
+    root_window
+        .manage_substructure()
+        .expect("another window manager is already running");
+
     root_window.grab_key("l", Modifier::Alt); // Press Alt + L to put the window on the top
     root_window.grab_key("r", Modifier::Alt); // Press Alt + R to put the window on the bottom
 
@@ -49,6 +56,19 @@ fn main() {
                 }
             }
 
+            // New windows show up under the cursor
+            EventType::MapRequest(request) => {
+                let cursor_position = root_window.get_cursor_position();
+                request.window.set_position(cursor_position);
+
+                // The window could have been closed in the meantime, that's fine
+                request.grant().ok();
+            }
+
+            EventType::ConfigureRequest(request) => {
+                request.grant().ok();
+            }
+
             EventType::MouseButtonPress(mouse_event) => {
                 if let Some(window) = event.subwindow {
                     attributes = Some(window.get_data());
@@ -1,51 +1,382 @@
-use std::cmp;
+pub mod analysis;
+pub mod zones;
 
-// TODO: Adding a HSV/SV TO RGB conversion, I don't know if i will do that, because X11
-// color values are in RGB, so you will likely already have the RGB values
+use std::{fmt, str::FromStr};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct RGB {
     pub r: u8,
     pub g: u8,
     pub b: u8,
 }
 
+/// An `RGB` color with transparency, `a` is `0` for fully transparent and `255` for fully
+/// opaque
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RGBA {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// Hue, saturation and value. `h` is in degrees, from `0` to `360`, `s` and `v` go from
+/// `0` to `1`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct HSV {
     pub h: f32,
     pub s: f32,
     pub v: f32,
 }
 
+/// Hue, saturation and lightness. `h` is in degrees, from `0` to `360`, `s` and `l` go
+/// from `0` to `1`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HSL {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+/// Why a hex color could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// Hex colors have to start with `#`
+    MissingHash,
+
+    /// The number of digits after the `#`
+    InvalidLength(usize),
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseColorError::MissingHash => write!(f, "hex colors have to start with `#`"),
+            ParseColorError::InvalidLength(length) => {
+                write!(f, "a hex color can't have {} digits", length)
+            }
+            ParseColorError::InvalidDigit(digit) => write!(f, "`{}` is not a hex digit", digit),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// The hue in degrees, shared by HSV and HSL, `max` and `min` are the biggest and
+/// smallest channel, from `0` to `1`
+fn hue(r: f32, g: f32, b: f32, max: f32, min: f32) -> f32 {
+    let delta = max - min;
+
+    if delta == 0. {
+        return 0.;
+    }
+
+    let h = if max == r {
+        (g - b) / delta
+    } else if max == g {
+        (b - r) / delta + 2.
+    } else {
+        (r - g) / delta + 4.
+    };
+
+    (h * 60.).rem_euclid(360.)
+}
+
+/// Turns a hue, a chroma (how far the color is from gray) and the value of the smallest
+/// channel back into RGB, shared by HSV and HSL
+fn from_hue(h: f32, chroma: f32, min: f32) -> RGB {
+    let h = h.rem_euclid(360.) / 60.;
+    let x = chroma * (1. - (h % 2. - 1.).abs());
+
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x),
+    };
+
+    let to_u8 = |channel: f32| ((channel + min) * 255.).round().clamp(0., 255.) as u8;
+
+    RGB {
+        r: to_u8(r),
+        g: to_u8(g),
+        b: to_u8(b),
+    }
+}
+
+fn normalize(rgb: RGB) -> (f32, f32, f32, f32, f32) {
+    let (r, g, b) = (
+        rgb.r as f32 / 255.,
+        rgb.g as f32 / 255.,
+        rgb.b as f32 / 255.,
+    );
+
+    (r, g, b, r.max(g).max(b), r.min(g).min(b))
+}
+
+/// # Examples
+/// ```
+/// use undici::color::{HSV, RGB};
+///
+/// let hsv = HSV::from(RGB { r: 0, g: 0, b: 255 });
+/// assert_eq!((hsv.h, hsv.s, hsv.v), (240., 1., 1.));
+///
+/// // Every color survives the round trip
+/// for rgb in [
+///     RGB { r: 255, g: 128, b: 0 },
+///     RGB { r: 12, g: 200, b: 99 },
+///     RGB { r: 200, g: 10, b: 140 },
+///     RGB { r: 77, g: 77, b: 77 },
+/// ] {
+///     assert_eq!(RGB::from(HSV::from(rgb)), rgb);
+/// }
+/// ```
 impl From<RGB> for HSV {
     fn from(rgb: RGB) -> Self {
-        // https://www.had2know.org/technology/hsv-rgb-conversion-formula-calculator.html
+        let (r, g, b, max, min) = normalize(rgb);
 
-        let max = cmp::max(cmp::max(rgb.r, rgb.g), rgb.b);
-        let min = cmp::min(cmp::min(rgb.r, rgb.g), rgb.b);
+        HSV {
+            h: hue(r, g, b, max, min),
+            s: if max > 0. { (max - min) / max } else { 0. },
+            v: max,
+        }
+    }
+}
 
-        let v = max as f32 / 255.;
+/// # Examples
+/// ```
+/// use undici::color::{HSV, RGB};
+///
+/// let orange = RGB::from(HSV { h: 30., s: 1., v: 1. });
+/// assert_eq!(orange, RGB { r: 255, g: 128, b: 0 });
+/// ```
+impl From<HSV> for RGB {
+    fn from(hsv: HSV) -> Self {
+        let s = hsv.s.clamp(0., 1.);
+        let v = hsv.v.clamp(0., 1.);
+        let chroma = v * s;
 
-        let s = if max > 0 {
-            1. - (min as f32 / max as f32)
-        } else {
-            0.
-        };
+        from_hue(hsv.h, chroma, v - chroma)
+    }
+}
 
-        let expression = f32::acos(
-            ((rgb.r as f32) - (rgb.g as f32 * 0.5) - (rgb.b as f32 * 0.5))
-                / f32::sqrt(
-                    (rgb.r as f32).powf(2.) + (rgb.g as f32).powf(2.) + (rgb.b as f32).powf(2.)
-                        - (rgb.r as f32 * rgb.g as f32)
-                        - (rgb.r as f32 * rgb.b as f32)
-                        - (rgb.g as f32 * rgb.b as f32),
-                ),
-        );
-
-        let h = if rgb.g >= rgb.b {
-            expression
+/// # Examples
+/// ```
+/// use undici::color::{HSL, RGB};
+///
+/// let hsl = HSL::from(RGB { r: 255, g: 0, b: 0 });
+/// assert_eq!((hsl.h, hsl.s, hsl.l), (0., 1., 0.5));
+///
+/// for rgb in [
+///     RGB { r: 255, g: 128, b: 0 },
+///     RGB { r: 12, g: 200, b: 99 },
+///     RGB { r: 200, g: 10, b: 140 },
+///     RGB { r: 0, g: 0, b: 0 },
+/// ] {
+///     assert_eq!(RGB::from(HSL::from(rgb)), rgb);
+/// }
+/// ```
+impl From<RGB> for HSL {
+    fn from(rgb: RGB) -> Self {
+        let (r, g, b, max, min) = normalize(rgb);
+        let l = (max + min) / 2.;
+
+        let s = if max == min {
+            0.
         } else {
-            360. - expression
+            (max - min) / (1. - (2. * l - 1.).abs())
         };
 
-        HSV { h, s, v }
+        HSL {
+            h: hue(r, g, b, max, min),
+            s,
+            l,
+        }
+    }
+}
+
+impl From<HSL> for RGB {
+    fn from(hsl: HSL) -> Self {
+        let s = hsl.s.clamp(0., 1.);
+        let l = hsl.l.clamp(0., 1.);
+        let chroma = (1. - (2. * l - 1.).abs()) * s;
+
+        from_hue(hsl.h, chroma, l - chroma / 2.)
+    }
+}
+
+impl From<HSV> for HSL {
+    fn from(hsv: HSV) -> Self {
+        RGB::from(hsv).into()
+    }
+}
+
+impl From<HSL> for HSV {
+    fn from(hsl: HSL) -> Self {
+        RGB::from(hsl).into()
+    }
+}
+
+/// Fully opaque
+impl From<RGB> for RGBA {
+    fn from(rgb: RGB) -> Self {
+        RGBA {
+            r: rgb.r,
+            g: rgb.g,
+            b: rgb.b,
+            a: u8::MAX,
+        }
+    }
+}
+
+/// Throws the transparency away
+impl From<RGBA> for RGB {
+    fn from(rgba: RGBA) -> Self {
+        RGB {
+            r: rgba.r,
+            g: rgba.g,
+            b: rgba.b,
+        }
+    }
+}
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` into channels, the alpha is `255`
+/// when it's missing
+fn parse_hex(string: &str) -> Result<[u8; 4], ParseColorError> {
+    let digits = string
+        .strip_prefix('#')
+        .ok_or(ParseColorError::MissingHash)?;
+
+    let values = digits
+        .chars()
+        .map(|digit| {
+            digit
+                .to_digit(16)
+                .map(|value| value as u8)
+                .ok_or(ParseColorError::InvalidDigit(digit))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let mut channels: Vec<u8> = match values.len() {
+        // `#f80` is short for `#ff8800`
+        3 | 4 => values.iter().map(|value| value * 17).collect(),
+        6 | 8 => values
+            .chunks_exact(2)
+            .map(|pair| pair[0] * 16 + pair[1])
+            .collect(),
+        length => return Err(ParseColorError::InvalidLength(length)),
+    };
+
+    channels.resize(4, u8::MAX);
+
+    Ok([channels[0], channels[1], channels[2], channels[3]])
+}
+
+/// # Examples
+/// ```
+/// use undici::color::{ParseColorError, RGB};
+///
+/// assert_eq!("#ff8000".parse(), Ok(RGB { r: 255, g: 128, b: 0 }));
+/// assert_eq!("#f80".parse(), Ok(RGB { r: 255, g: 136, b: 0 }));
+/// assert_eq!("ff8000".parse::<RGB>(), Err(ParseColorError::MissingHash));
+/// assert_eq!("#ff80".parse::<RGB>(), Err(ParseColorError::InvalidLength(4)));
+/// ```
+impl FromStr for RGB {
+    type Err = ParseColorError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match parse_hex(string)? {
+            [r, g, b, _] if !matches!(string.len(), 5 | 9) => Ok(RGB { r, g, b }),
+            _ => Err(ParseColorError::InvalidLength(string.len() - 1)),
+        }
+    }
+}
+
+/// Like `RGB`'s, but also accepts `#rgba` and `#rrggbbaa`
+/// # Examples
+/// ```
+/// use undici::color::RGBA;
+///
+/// assert_eq!("#ff000080".parse(), Ok(RGBA { r: 255, g: 0, b: 0, a: 128 }));
+/// assert_eq!("#f00".parse(), Ok(RGBA { r: 255, g: 0, b: 0, a: 255 }));
+/// ```
+impl FromStr for RGBA {
+    type Err = ParseColorError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let [r, g, b, a] = parse_hex(string)?;
+
+        Ok(RGBA { r, g, b, a })
+    }
+}
+
+/// Formats the color as `#rrggbb`, which can be parsed back
+/// # Examples
+/// ```
+/// use undici::color::RGB;
+///
+/// let color = RGB { r: 255, g: 128, b: 0 };
+///
+/// assert_eq!(color.to_string(), "#ff8000");
+/// assert_eq!(color.to_string().parse(), Ok(color));
+/// ```
+impl fmt::Display for RGB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Formats the color as `#rrggbbaa`
+impl fmt::Display for RGBA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{:02x}{:02x}{:02x}{:02x}",
+            self.r, self.g, self.b, self.a
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every third value of every channel, which still covers `0` and `255`. The whole
+    /// cube passes too, it's just too slow for a debug build
+    fn cube() -> impl Iterator<Item = RGB> {
+        let values = || (0..=255u8).step_by(3);
+
+        values()
+            .flat_map(move |r| values().flat_map(move |g| values().map(move |b| RGB { r, g, b })))
+    }
+
+    #[test]
+    fn hsv_round_trip() {
+        for rgb in cube() {
+            assert_eq!(RGB::from(HSV::from(rgb)), rgb);
+        }
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        for rgb in cube() {
+            assert_eq!(RGB::from(HSL::from(rgb)), rgb);
+        }
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        for rgb in cube() {
+            assert_eq!(rgb.to_string().parse(), Ok(rgb));
+
+            let rgba = RGBA {
+                a: rgb.g ^ rgb.b,
+                ..rgb.into()
+            };
+            assert_eq!(rgba.to_string().parse(), Ok(rgba));
+        }
     }
 }
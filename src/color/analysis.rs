@@ -0,0 +1,308 @@
+//! Colors that describe a whole image, like its average or its palette, for example to
+//! match some lights to what's on the screen
+
+use super::RGB;
+use crate::x11::{common::Rect, image::Image};
+use std::cmp::Reverse;
+
+/// How many pixels of each row and column the analysis looks at, big images have way
+/// more pixels than needed for a good guess of their colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sampling {
+    step: u32,
+}
+
+impl Sampling {
+    /// Every single pixel
+    pub const ALL: Sampling = Sampling { step: 1 };
+
+    /// One pixel every `step` pixels, both horizontally and vertically, so `every(4)`
+    /// looks at 1/16th of the image
+    pub fn every(step: u32) -> Sampling {
+        Sampling { step: step.max(1) }
+    }
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Sampling::ALL
+    }
+}
+
+/// How many bits of each channel the histogram keeps, 4 makes 4096 bins, which is
+/// enough to tell colors apart without splitting a gradient into hundreds of them
+const HISTOGRAM_BITS: u32 = 4;
+
+/// More rounds hardly ever move the centers
+const K_MEANS_ROUNDS: usize = 16;
+
+fn samples(image: &Image, sampling: Sampling) -> impl Iterator<Item = RGB> + '_ {
+    region_samples(image, Rect::new(0, 0, image.width, image.height), sampling)
+}
+
+/// The pixels of `region` that the sampling picks, the parts of `region` that are
+/// outside of the image are skipped, and only the picked pixels are decoded, straight
+/// from the rows of the image
+fn region_samples(
+    image: &Image,
+    region: Rect,
+    sampling: Sampling,
+) -> impl Iterator<Item = RGB> + '_ {
+    let clamp = |value: i64, max: u32| value.clamp(0, max as i64) as u32;
+
+    let x_range = clamp(region.x as i64, image.width)
+        ..clamp(region.x as i64 + region.width as i64, image.width);
+    let y_range = clamp(region.y as i64, image.height)
+        ..clamp(region.y as i64 + region.height as i64, image.height);
+
+    let step = sampling.step as usize;
+
+    image
+        .rows()
+        .enumerate()
+        .skip(y_range.start as usize)
+        .take(y_range.len())
+        .step_by(step)
+        .flat_map(move |(y, row)| {
+            x_range
+                .clone()
+                .step_by(step)
+                .map(move |x| image.row_pixel(row, x, y as u32))
+        })
+}
+
+/// Screens store colors with the sRGB curve, averaging those values directly makes
+/// the result too dark, so we average the actual light instead
+fn to_linear(channel: u8) -> f32 {
+    let channel = channel as f32 / 255.;
+
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(channel: f32) -> u8 {
+    let channel = if channel <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1. / 2.4) - 0.055
+    };
+
+    (channel * 255.).round().clamp(0., 255.) as u8
+}
+
+/// A running sum of colors, to average them
+#[derive(Clone, Copy, Default)]
+struct Sum {
+    r: u64,
+    g: u64,
+    b: u64,
+    count: u64,
+}
+
+impl Sum {
+    fn add(&mut self, color: RGB) {
+        self.r += color.r as u64;
+        self.g += color.g as u64;
+        self.b += color.b as u64;
+        self.count += 1;
+    }
+
+    fn average(&self) -> RGB {
+        let count = self.count.max(1);
+
+        RGB {
+            r: ((self.r + count / 2) / count) as u8,
+            g: ((self.g + count / 2) / count) as u8,
+            b: ((self.b + count / 2) / count) as u8,
+        }
+    }
+}
+
+/// The average color of the image, weighted by how much light each pixel gives off,
+/// which is what the image looks like from far away. Black for an empty image
+/// # Examples
+/// ```
+/// use undici::{
+///     color::{analysis::{average, Sampling}, RGB},
+///     x11::image::Image,
+/// };
+///
+/// // Half black and half white looks like a light gray, not like `#808080`
+/// let image = Image::from_rgb(2, 1, &[0, 0, 0, 255, 255, 255]);
+///
+/// assert_eq!(average(&image, Sampling::ALL), RGB { r: 188, g: 188, b: 188 });
+/// ```
+pub fn average(image: &Image, sampling: Sampling) -> RGB {
+    average_region(image, Rect::new(0, 0, image.width, image.height), sampling)
+}
+
+/// Like `average()`, but only of a part of the image
+/// # Examples
+/// ```
+/// use undici::{
+///     color::{analysis::{average_region, Sampling}, RGB},
+///     x11::{common::Rect, image::Image},
+/// };
+///
+/// let image = Image::from_rgb(2, 1, &[0, 0, 0, 255, 255, 255]);
+/// let right_half = Rect::new(1, 0, 1, 1);
+///
+/// assert_eq!(average_region(&image, right_half, Sampling::ALL), RGB { r: 255, g: 255, b: 255 });
+/// ```
+pub fn average_region(image: &Image, region: Rect, sampling: Sampling) -> RGB {
+    let mut sum = [0f32; 3];
+    let mut count = 0;
+
+    for color in region_samples(image, region, sampling) {
+        sum[0] += to_linear(color.r);
+        sum[1] += to_linear(color.g);
+        sum[2] += to_linear(color.b);
+        count += 1;
+    }
+
+    if count == 0 {
+        return RGB::default();
+    }
+
+    RGB {
+        r: from_linear(sum[0] / count as f32),
+        g: from_linear(sum[1] / count as f32),
+        b: from_linear(sum[2] / count as f32),
+    }
+}
+
+/// Groups similar colors together and sums them, sorted from the most common group
+fn histogram(pixels: impl IntoIterator<Item = RGB>) -> Vec<Sum> {
+    let shift = 8 - HISTOGRAM_BITS;
+    let mut bins = vec![Sum::default(); 1 << (HISTOGRAM_BITS * 3)];
+
+    for color in pixels {
+        let bin = (color.r as usize >> shift) << (HISTOGRAM_BITS * 2)
+            | (color.g as usize >> shift) << HISTOGRAM_BITS
+            | color.b as usize >> shift;
+
+        bins[bin].add(color);
+    }
+
+    bins.retain(|bin| bin.count > 0);
+    bins.sort_by_key(|bin| Reverse(bin.count));
+
+    bins
+}
+
+/// The most common color of the image, unlike the average this is a color that's
+/// actually on the screen. Similar shades count as the same color, and the result is
+/// their average. Black for an empty image
+/// # Examples
+/// ```
+/// use undici::{
+///     color::{analysis::{dominant_color, Sampling}, RGB},
+///     x11::image::Image,
+/// };
+///
+/// // Two shades of red and a blue pixel
+/// let image = Image::from_rgb(3, 1, &[250, 0, 0, 252, 2, 0, 0, 0, 255]);
+///
+/// assert_eq!(dominant_color(&image, Sampling::ALL), RGB { r: 251, g: 1, b: 0 });
+/// ```
+pub fn dominant_color(image: &Image, sampling: Sampling) -> RGB {
+    histogram(samples(image, sampling))
+        .first()
+        .map(Sum::average)
+        .unwrap_or_default()
+}
+
+fn distance(a: RGB, b: RGB) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+
+    channel(a.r, b.r) + channel(a.g, b.g) + channel(a.b, b.b)
+}
+
+/// Sums up the pixels closest to each center, `centers` can't be empty unless `pixels` is
+fn closest_pixels(pixels: &[RGB], centers: &[RGB]) -> Vec<Sum> {
+    let mut sums = vec![Sum::default(); centers.len()];
+
+    for pixel in pixels {
+        let closest = (0..centers.len())
+            .min_by_key(|center| distance(*pixel, centers[*center]))
+            .expect("there is at least a center for every pixel");
+
+        sums[closest].add(*pixel);
+    }
+
+    sums
+}
+
+/// The `count` colors that best describe the image, from the most to the least common,
+/// found with k-means. It always gives the same result for the same image, and fewer
+/// than `count` colors if the image doesn't have that many
+/// # Examples
+/// ```
+/// use undici::{
+///     color::{analysis::{palette, Sampling}, RGB},
+///     x11::image::Image,
+/// };
+///
+/// // Three red pixels and a blue one
+/// let image = Image::from_rgb(2, 2, &[
+///     255, 0, 0, 250, 0, 0,
+///     255, 0, 0, 0, 0, 255,
+/// ]);
+///
+/// let colors = palette(&image, 2, Sampling::ALL);
+///
+/// assert_eq!(colors, vec![RGB { r: 253, g: 0, b: 0 }, RGB { r: 0, g: 0, b: 255 }]);
+/// assert!(palette(&image, 0, Sampling::ALL).is_empty());
+/// ```
+pub fn palette(image: &Image, count: usize, sampling: Sampling) -> Vec<RGB> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    // Every round goes through all of the pixels, so they are only decoded once
+    let pixels: Vec<RGB> = samples(image, sampling).collect();
+
+    // Starting from the most common colors, instead of random ones, makes the result
+    // the same every time, and needs fewer rounds
+    let mut centers: Vec<RGB> = histogram(pixels.iter().copied())
+        .iter()
+        .take(count)
+        .map(Sum::average)
+        .collect();
+
+    // Always the pixels closest to each of the current `centers`
+    let mut sums = closest_pixels(&pixels, &centers);
+
+    for _ in 0..K_MEANS_ROUNDS {
+        let new_centers: Vec<RGB> = sums
+            .iter()
+            .zip(&centers)
+            .map(|(sum, center)| {
+                if sum.count > 0 {
+                    sum.average()
+                } else {
+                    *center
+                }
+            })
+            .collect();
+
+        if new_centers == centers {
+            break;
+        }
+
+        centers = new_centers;
+        sums = closest_pixels(&pixels, &centers);
+    }
+
+    let mut colors: Vec<(RGB, u64)> = centers
+        .into_iter()
+        .zip(sums.iter().map(|sum| sum.count))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+
+    colors.sort_by_key(|(_, count)| Reverse(*count));
+    colors.into_iter().map(|(color, _)| color).collect()
+}
@@ -0,0 +1,179 @@
+//! The colors along the edges of the screen, for LED strips behind monitors and other
+//! bias lighting
+
+use super::{
+    analysis::{average_region, Sampling},
+    RGB,
+};
+use crate::x11::{common::Rect, image::Image};
+
+/// An edge of the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// How the LEDs are laid out around the screen: how many zones each edge has, how deep
+/// into the screen a zone goes, and in what order the LEDs are wired. Can be created with
+/// `ZoneLayout::new()`
+/// # Examples
+/// A strip that starts at the bottom left corner and goes up
+/// ```
+/// use undici::color::zones::{Edge, ZoneLayout};
+///
+/// let layout = ZoneLayout::new(16, 9, 16, 9)
+///     .depth(100)
+///     .start(Edge::Left)
+///     .clockwise(true);
+///
+/// assert_eq!(layout.zones(1920, 1080).len(), 16 + 9 + 16 + 9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneLayout {
+    top: u32,
+    right: u32,
+    bottom: u32,
+    left: u32,
+    depth: u32,
+    start: Edge,
+    clockwise: bool,
+    sampling: Sampling,
+}
+
+impl ZoneLayout {
+    /// How many zones each edge has, an edge can have none. By default the zones are
+    /// 64 pixels deep, and the order starts at the left end of the top edge and goes
+    /// clockwise
+    pub fn new(top: u32, right: u32, bottom: u32, left: u32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+            depth: 64,
+            start: Edge::Top,
+            clockwise: true,
+            sampling: Sampling::ALL,
+        }
+    }
+
+    /// How many pixels from the edge towards the center a zone covers
+    pub fn depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// The edge the first LED is on. The first LED is at the end of the edge the order
+    /// comes from, so clockwise the `Left` edge starts at the bottom, while
+    /// counterclockwise it starts at the top
+    pub fn start(mut self, start: Edge) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn clockwise(mut self, clockwise: bool) -> Self {
+        self.clockwise = clockwise;
+        self
+    }
+
+    /// Zones are usually big, so skipping pixels barely changes their color
+    pub fn sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// The area every zone covers on a `width`x`height` screen, in LED order
+    pub fn zones(&self, width: u32, height: u32) -> Vec<Rect> {
+        let clockwise_edges = [Edge::Top, Edge::Right, Edge::Bottom, Edge::Left];
+        let start = clockwise_edges
+            .iter()
+            .position(|edge| *edge == self.start)
+            .expect("every edge is in the list");
+
+        let edges: Vec<Edge> = (0..4)
+            .map(|index| match self.clockwise {
+                true => clockwise_edges[(start + index) % 4],
+                false => clockwise_edges[(start + 4 - index) % 4],
+            })
+            .collect();
+
+        edges
+            .into_iter()
+            .flat_map(|edge| {
+                let mut zones = self.edge_zones(edge, width, height);
+
+                // Clockwise the bottom and left edges go backwards, and counterclockwise
+                // the top and right ones do
+                let backwards = matches!(edge, Edge::Bottom | Edge::Left) == self.clockwise;
+                if backwards {
+                    zones.reverse();
+                }
+
+                zones
+            })
+            .collect()
+    }
+
+    /// The zones of `edge`, from left to right or from top to bottom
+    fn edge_zones(&self, edge: Edge, width: u32, height: u32) -> Vec<Rect> {
+        let depth = self.depth.min(width).min(height);
+
+        let (count, length) = match edge {
+            Edge::Top => (self.top, width),
+            Edge::Bottom => (self.bottom, width),
+            Edge::Right => (self.right, height),
+            Edge::Left => (self.left, height),
+        };
+
+        (0..count)
+            .map(|index| {
+                // Computed from the ends instead of with a fixed size, so the rounding
+                // doesn't leave a gap at the end of the edge
+                let start = (length as u64 * index as u64 / count as u64) as u32;
+                let end = (length as u64 * (index as u64 + 1) / count as u64) as u32;
+
+                match edge {
+                    Edge::Top => Rect::new(start as i32, 0, end - start, depth),
+                    Edge::Bottom => {
+                        Rect::new(start as i32, (height - depth) as i32, end - start, depth)
+                    }
+                    Edge::Left => Rect::new(0, start as i32, depth, end - start),
+                    Edge::Right => {
+                        Rect::new((width - depth) as i32, start as i32, depth, end - start)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The average color of every zone, in LED order. Only the pixels inside the zones
+    /// are decoded
+    /// # Examples
+    /// ```
+    /// use undici::{
+    ///     color::{zones::{Edge, ZoneLayout}, RGB},
+    ///     x11::image::Image,
+    /// };
+    ///
+    /// // A 2x2 image, red on the left and blue on the right
+    /// let image = Image::from_rgb(2, 2, &[
+    ///     255, 0, 0, 0, 0, 255,
+    ///     255, 0, 0, 0, 0, 255,
+    /// ]);
+    ///
+    /// let layout = ZoneLayout::new(0, 1, 0, 1).depth(1).start(Edge::Left);
+    /// let red = RGB { r: 255, g: 0, b: 0 };
+    /// let blue = RGB { r: 0, g: 0, b: 255 };
+    ///
+    /// assert_eq!(layout.colors(&image), vec![red, blue]);
+    /// ```
+    pub fn colors(&self, image: &Image) -> Vec<RGB> {
+        self.zones(image.width, image.height)
+            .into_iter()
+            .map(|zone| average_region(image, zone, self.sampling))
+            .collect()
+    }
+}
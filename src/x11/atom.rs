@@ -0,0 +1,184 @@
+use super::{
+    display::Display,
+    errors::{checked_reply, ProtocolError},
+};
+use std::{
+    collections::HashMap,
+    ffi::{c_char, CStr, CString},
+};
+use x11::xlib::*;
+
+/// A unique id the X server gives to a string, atoms are used to name properties, their
+/// types, selections and client messages. Can be created with `Display`'s
+/// `.intern_atom()` method, or from one of the predefined constants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Atom(pub(crate) u64);
+
+/// Interned atoms never change for the lifetime of the X server, so we can remember them
+#[derive(Default)]
+pub(crate) struct AtomCache {
+    atoms: HashMap<String, Atom>,
+    names: HashMap<Atom, String>,
+}
+
+impl AtomCache {
+    fn insert(&mut self, name: &str, atom: Atom) {
+        self.atoms.insert(name.to_string(), atom);
+        self.names.insert(atom, name.to_string());
+    }
+}
+
+/// These atoms are built into the X protocol, so they don't need to be interned
+impl Atom {
+    pub const PRIMARY: Atom = Atom(XA_PRIMARY);
+    pub const SECONDARY: Atom = Atom(XA_SECONDARY);
+    pub const ARC: Atom = Atom(XA_ARC);
+    pub const ATOM: Atom = Atom(XA_ATOM);
+    pub const BITMAP: Atom = Atom(XA_BITMAP);
+    pub const CARDINAL: Atom = Atom(XA_CARDINAL);
+    pub const COLORMAP: Atom = Atom(XA_COLORMAP);
+    pub const CURSOR: Atom = Atom(XA_CURSOR);
+    pub const CUT_BUFFER0: Atom = Atom(XA_CUT_BUFFER0);
+    pub const CUT_BUFFER1: Atom = Atom(XA_CUT_BUFFER1);
+    pub const CUT_BUFFER2: Atom = Atom(XA_CUT_BUFFER2);
+    pub const CUT_BUFFER3: Atom = Atom(XA_CUT_BUFFER3);
+    pub const CUT_BUFFER4: Atom = Atom(XA_CUT_BUFFER4);
+    pub const CUT_BUFFER5: Atom = Atom(XA_CUT_BUFFER5);
+    pub const CUT_BUFFER6: Atom = Atom(XA_CUT_BUFFER6);
+    pub const CUT_BUFFER7: Atom = Atom(XA_CUT_BUFFER7);
+    pub const DRAWABLE: Atom = Atom(XA_DRAWABLE);
+    pub const FONT: Atom = Atom(XA_FONT);
+    pub const INTEGER: Atom = Atom(XA_INTEGER);
+    pub const PIXMAP: Atom = Atom(XA_PIXMAP);
+    pub const POINT: Atom = Atom(XA_POINT);
+    pub const RECTANGLE: Atom = Atom(XA_RECTANGLE);
+    pub const RESOURCE_MANAGER: Atom = Atom(XA_RESOURCE_MANAGER);
+    pub const RGB_COLOR_MAP: Atom = Atom(XA_RGB_COLOR_MAP);
+    pub const RGB_BEST_MAP: Atom = Atom(XA_RGB_BEST_MAP);
+    pub const RGB_BLUE_MAP: Atom = Atom(XA_RGB_BLUE_MAP);
+    pub const RGB_DEFAULT_MAP: Atom = Atom(XA_RGB_DEFAULT_MAP);
+    pub const RGB_GRAY_MAP: Atom = Atom(XA_RGB_GRAY_MAP);
+    pub const RGB_GREEN_MAP: Atom = Atom(XA_RGB_GREEN_MAP);
+    pub const RGB_RED_MAP: Atom = Atom(XA_RGB_RED_MAP);
+    pub const STRING: Atom = Atom(XA_STRING);
+    pub const VISUALID: Atom = Atom(XA_VISUALID);
+    pub const WINDOW: Atom = Atom(XA_WINDOW);
+    pub const WM_COMMAND: Atom = Atom(XA_WM_COMMAND);
+    pub const WM_HINTS: Atom = Atom(XA_WM_HINTS);
+    pub const WM_CLIENT_MACHINE: Atom = Atom(XA_WM_CLIENT_MACHINE);
+    pub const WM_ICON_NAME: Atom = Atom(XA_WM_ICON_NAME);
+    pub const WM_ICON_SIZE: Atom = Atom(XA_WM_ICON_SIZE);
+    pub const WM_NAME: Atom = Atom(XA_WM_NAME);
+    pub const WM_NORMAL_HINTS: Atom = Atom(XA_WM_NORMAL_HINTS);
+    pub const WM_SIZE_HINTS: Atom = Atom(XA_WM_SIZE_HINTS);
+    pub const WM_ZOOM_HINTS: Atom = Atom(XA_WM_ZOOM_HINTS);
+    pub const WM_CLASS: Atom = Atom(XA_WM_CLASS);
+    pub const WM_TRANSIENT_FOR: Atom = Atom(XA_WM_TRANSIENT_FOR);
+
+    /// The raw value of the atom, this is what `xprop` and `xlsatoms` show
+    pub fn id(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display {
+    /// Gets the atom with that name, creating it if it doesn't exist yet. The result is
+    /// cached, so only the first call for every name talks to the X server
+    /// # Examples
+    /// ```
+    /// use undici::x11::{atom::Atom, display::Display};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// let net_wm_pid = display.intern_atom("_NET_WM_PID").unwrap();
+    /// assert_eq!(display.atom_name(net_wm_pid).unwrap(), "_NET_WM_PID");
+    ///
+    /// // Predefined atoms are the same ones the server gives out
+    /// assert_eq!(display.intern_atom("WM_NAME").unwrap(), Atom::WM_NAME);
+    /// ```
+    /// # Panics
+    /// If `name` contains a nul byte
+    pub fn intern_atom(&self, name: &str) -> Result<Atom, ProtocolError> {
+        Ok(self.intern_atoms(&[name])?[0])
+    }
+
+    /// Like `intern_atom`, but asks for all of the names that are not cached yet in a
+    /// single round trip, the atoms are returned in the same order as `names`
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// let atoms = display
+    ///     .intern_atoms(&["_NET_WM_NAME", "_NET_WM_STATE", "UTF8_STRING"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(atoms.len(), 3);
+    /// ```
+    /// # Panics
+    /// If any of the `names` contains a nul byte
+    pub fn intern_atoms(&self, names: &[&str]) -> Result<Vec<Atom>, ProtocolError> {
+        let mut cache = self.atom_cache.borrow_mut();
+
+        let mut missing: Vec<&str> = Vec::new();
+        for name in names {
+            if !cache.atoms.contains_key(*name) && !missing.contains(name) {
+                missing.push(name);
+            }
+        }
+
+        if !missing.is_empty() {
+            let missing_c: Vec<CString> = missing
+                .iter()
+                .map(|name| CString::new(*name).unwrap())
+                .collect();
+            let mut missing_c_p: Vec<*mut c_char> = missing_c
+                .iter()
+                .map(|name| name.as_ptr() as *mut c_char)
+                .collect();
+            let mut atoms = vec![0; missing.len()];
+
+            checked_reply(self.display, || unsafe {
+                XInternAtoms(
+                    self.display,
+                    missing_c_p.as_mut_ptr(),
+                    missing_c_p.len() as i32,
+                    false.into(),
+                    atoms.as_mut_ptr(),
+                )
+            })?;
+
+            for (name, atom) in missing.iter().zip(atoms) {
+                cache.insert(name, Atom(atom));
+            }
+        }
+
+        Ok(names.iter().map(|name| cache.atoms[*name]).collect())
+    }
+
+    /// The name of an atom, this is cached like `intern_atom`. Fails with a `BadAtom`
+    /// error if the atom doesn't exist
+    pub fn atom_name(&self, atom: Atom) -> Result<String, ProtocolError> {
+        if let Some(name) = self.atom_cache.borrow().names.get(&atom) {
+            return Ok(name.clone());
+        }
+
+        let name_c = checked_reply(self.display, || unsafe {
+            XGetAtomName(self.display, atom.0)
+        })?;
+
+        if name_c.is_null() {
+            return Ok(String::new());
+        }
+
+        let name = unsafe {
+            let name = CStr::from_ptr(name_c).to_string_lossy().into_owned();
+            XFree(name_c as *mut std::ffi::c_void);
+            name
+        };
+
+        self.atom_cache.borrow_mut().insert(&name, atom);
+        Ok(name)
+    }
+}
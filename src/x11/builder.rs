@@ -0,0 +1,201 @@
+use super::{
+    common::{EventMask, Vector2},
+    display::Display,
+    errors::{checked, ProtocolError},
+    screen::Visual,
+    window::{OwnedWindow, Window},
+};
+use std::ptr::null_mut;
+use x11::xlib::{
+    AllocNone, CWBackPixel, CWBorderPixel, CWColormap, CWEventMask, CWOverrideRedirect,
+    CopyFromParent, InputOutput, XCreateColormap, XCreateWindow, XFreeColormap,
+    XSetWindowAttributes,
+};
+
+/// Can be created with `Display`'s `.create_window()` method, every setting is optional
+pub struct WindowBuilder<'a> {
+    display: &'a Display,
+    parent: Window,
+    position: Vector2<i32>,
+    scale: Vector2<u32>,
+    border_width: u32,
+    border_color: Option<u64>,
+    background: Option<u64>,
+    event_mask: EventMask,
+    override_redirect: bool,
+    visual: Option<(Visual, i32)>,
+}
+
+impl Display {
+    /// Creates a new window, by default it's a 1x1 child of the root window, and it will
+    /// not be visible until you `map()` it
+    /// # Examples
+    /// ```
+    /// use undici::x11::{
+    ///     common::{EventMask, Vector2},
+    ///     display::Display,
+    /// };
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screen = display.default_screen();
+    ///
+    /// let window = display
+    ///     .create_window()
+    ///     .position(Vector2::new(100, 100))
+    ///     .scale(Vector2::new(300, 200))
+    ///     .border_width(2)
+    ///     .border_color(screen.white_pixel)
+    ///     .background(screen.black_pixel)
+    ///     .event_mask(EventMask::EXPOSURE | EventMask::KEY_PRESS)
+    ///     .build()
+    ///     .expect("could not create window");
+    ///
+    /// window.map();
+    ///
+    /// let data = window.get_data();
+    /// assert_eq!(data.scale.x, 300);
+    /// assert_eq!(data.scale.y, 200);
+    ///
+    /// // The window gets destroyed here
+    /// drop(window);
+    /// ```
+    pub fn create_window(&self) -> WindowBuilder<'_> {
+        WindowBuilder {
+            display: self,
+            parent: self.get_root_window(),
+            position: Vector2::new(0, 0),
+            scale: Vector2::new(1, 1),
+            border_width: 0,
+            border_color: None,
+            background: None,
+            event_mask: EventMask::NONE,
+            override_redirect: false,
+            visual: None,
+        }
+    }
+}
+
+impl<'a> WindowBuilder<'a> {
+    /// The window the new window will live in, the root window by default
+    pub fn parent(mut self, parent: Window) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    /// Relative to the parent window
+    pub fn position(mut self, position: Vector2<i32>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Size of the window, not counting the border. Both sides have to be at least 1
+    pub fn scale(mut self, scale: Vector2<u32>) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn border_width(mut self, border_width: u32) -> Self {
+        self.border_width = border_width;
+        self
+    }
+
+    /// The pixel value of the border, for example `Screen.white_pixel`
+    pub fn border_color(mut self, pixel: u64) -> Self {
+        self.border_color = Some(pixel);
+        self
+    }
+
+    /// The pixel value the window gets filled with, without one the window just shows
+    /// whatever was behind it when it was mapped
+    pub fn background(mut self, pixel: u64) -> Self {
+        self.background = Some(pixel);
+        self
+    }
+
+    /// The events the window will report, this can be changed later with `select_input()`
+    pub fn event_mask(mut self, event_mask: EventMask) -> Self {
+        self.event_mask = event_mask;
+        self
+    }
+
+    /// Makes window managers ignore this window, useful for popups, menus and tooltips
+    pub fn override_redirect(mut self, override_redirect: bool) -> Self {
+        self.override_redirect = override_redirect;
+        self
+    }
+
+    /// Use a visual other than the parent's one, for example a 32 bit visual for
+    /// transparent windows. `depth` has to be one the visual supports
+    pub fn visual(mut self, visual: Visual, depth: i32) -> Self {
+        self.visual = Some((visual, depth));
+        self
+    }
+
+    pub fn build(self) -> Result<OwnedWindow<'a>, ProtocolError> {
+        let display = self.display.display;
+        let mut attributes: XSetWindowAttributes = unsafe { std::mem::zeroed() };
+        let mut value_mask = CWEventMask | CWOverrideRedirect;
+
+        attributes.event_mask = self.event_mask.0;
+        attributes.override_redirect = self.override_redirect.into();
+
+        if let Some(pixel) = self.background {
+            attributes.background_pixel = pixel;
+            value_mask |= CWBackPixel;
+        }
+
+        // A window with a different visual than its parent can't inherit the parent's
+        // colormap or border, X answers with a `BadMatch` if they are not set
+        let (visual, depth, colormap) = match self.visual {
+            Some((visual, depth)) => {
+                let colormap =
+                    unsafe { XCreateColormap(display, self.parent.id, visual.visual, AllocNone) };
+
+                attributes.colormap = colormap;
+                value_mask |= CWColormap | CWBorderPixel;
+
+                (visual.visual, depth, Some(colormap))
+            }
+
+            None => (null_mut(), CopyFromParent, None),
+        };
+
+        if let Some(pixel) = self.border_color {
+            attributes.border_pixel = pixel;
+            value_mask |= CWBorderPixel;
+        }
+
+        let id = checked(display, || unsafe {
+            XCreateWindow(
+                display,
+                self.parent.id,
+                self.position.x,
+                self.position.y,
+                self.scale.x,
+                self.scale.y,
+                self.border_width,
+                depth,
+                InputOutput as u32,
+                visual,
+                value_mask,
+                &mut attributes,
+            )
+        });
+
+        match id {
+            Ok(id) => Ok(OwnedWindow {
+                window: Window { id, display },
+                display: self.display,
+                colormap,
+            }),
+
+            Err(error) => {
+                if let Some(colormap) = colormap {
+                    unsafe { XFreeColormap(display, colormap) };
+                }
+
+                Err(error)
+            }
+        }
+    }
+}
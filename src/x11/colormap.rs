@@ -0,0 +1,233 @@
+use super::{
+    display::Display,
+    errors::{checked_reply, ColorError},
+    screen::Visual,
+};
+use crate::color::RGB;
+use std::{ffi::CString, ops::Deref};
+use x11::xlib::{
+    _XDisplay, AllocNone, XAllocColor, XAllocNamedColor, XColor, XCreateColormap, XDefaultColormap,
+    XDefaultScreen, XFreeColormap, XFreeColors, XParseColor, XRootWindow,
+};
+
+/// Turns pixel values into colors for windows that use it. On the usual `TrueColor`
+/// visuals every color is always there, on the others colors have to be allocated first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colormap {
+    pub(crate) id: u64,
+    pub(crate) display: *mut _XDisplay,
+}
+
+/// A colormap we created, it's freed when dropped, so it can't outlive its `Display`.
+/// Can be created with `Display`'s `.create_colormap()` method
+pub struct OwnedColormap<'a> {
+    colormap: Colormap,
+    display: &'a Display,
+}
+
+/// X colors have 16 bits per channel, `0xAB` becomes `0xABAB` so that `0xFF` stays white
+fn rgb_to_xcolor(color: RGB) -> XColor {
+    XColor {
+        pixel: 0,
+        red: color.r as u16 * 257,
+        green: color.g as u16 * 257,
+        blue: color.b as u16 * 257,
+        flags: 0,
+        pad: 0,
+    }
+}
+
+fn xcolor_to_rgb(xcolor: &XColor) -> RGB {
+    RGB {
+        r: (xcolor.red >> 8) as u8,
+        g: (xcolor.green >> 8) as u8,
+        b: (xcolor.blue >> 8) as u8,
+    }
+}
+
+impl Colormap {
+    /// The raw id of the colormap
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The pixel value of the closest color to `color` this colormap can show, which can
+    /// be used as a window's background or border
+    pub fn alloc_color(&self, color: RGB) -> Result<u64, ColorError> {
+        let mut xcolor = rgb_to_xcolor(color);
+
+        let status = checked_reply(self.display, || unsafe {
+            XAllocColor(self.display, self.id, &mut xcolor)
+        })?;
+
+        match status {
+            0 => Err(ColorError::CouldNotAllocate),
+            _ => Ok(xcolor.pixel),
+        }
+    }
+
+    /// Like `alloc_color()`, but with a color name from the X color database (like
+    /// `"steelblue"`) or a hex color (like `"#ff8800"`)
+    pub fn alloc_named_color(&self, name: &str) -> Result<u64, ColorError> {
+        let name_c = CString::new(name).map_err(|_| ColorError::UnknownName(name.to_string()))?;
+
+        // Xlib also gives back the exact color, which we don't need
+        let mut closest: XColor = unsafe { std::mem::zeroed() };
+        let mut exact: XColor = unsafe { std::mem::zeroed() };
+
+        let status = checked_reply(self.display, || unsafe {
+            XAllocNamedColor(
+                self.display,
+                self.id,
+                name_c.as_ptr(),
+                &mut closest,
+                &mut exact,
+            )
+        })?;
+
+        if status != 0 {
+            return Ok(closest.pixel);
+        }
+
+        // Xlib doesn't tell us why it failed, but if the name can be parsed the colormap
+        // is full
+        match self.lookup_named_color(name) {
+            Ok(_) => Err(ColorError::CouldNotAllocate),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// The color a name stands for, without allocating it
+    pub fn lookup_named_color(&self, name: &str) -> Result<RGB, ColorError> {
+        let name_c = CString::new(name).map_err(|_| ColorError::UnknownName(name.to_string()))?;
+        let mut xcolor: XColor = unsafe { std::mem::zeroed() };
+
+        let status = checked_reply(self.display, || unsafe {
+            XParseColor(self.display, self.id, name_c.as_ptr(), &mut xcolor)
+        })?;
+
+        match status {
+            0 => Err(ColorError::UnknownName(name.to_string())),
+            _ => Ok(xcolor_to_rgb(&xcolor)),
+        }
+    }
+
+    /// Gives back colors allocated with `alloc_color()` or `alloc_named_color()`, every
+    /// allocation has to be freed separately, even if they gave the same pixel
+    pub fn free_colors(&self, pixels: &[u64]) {
+        let mut pixels = pixels.to_vec();
+
+        unsafe {
+            XFreeColors(
+                self.display,
+                self.id,
+                pixels.as_mut_ptr(),
+                pixels.len() as i32,
+                0,
+            )
+        };
+    }
+}
+
+impl Display {
+    /// The colormap of the default screen, every window uses it unless it has a
+    /// different visual
+    pub fn default_colormap(&self) -> Colormap {
+        Colormap {
+            id: unsafe { XDefaultColormap(self.display, XDefaultScreen(self.display)) },
+            display: self.display,
+        }
+    }
+
+    /// Allocates `color` in the default colormap, see `Colormap::alloc_color()`
+    /// # Examples
+    /// ```
+    /// use undici::{color::RGB, x11::display::Display};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// let border_focus: RGB = "#ff8800".parse().unwrap();
+    /// let pixel = display
+    ///     .alloc_color(border_focus)
+    ///     .expect("could not allocate the color");
+    ///
+    /// let window = display
+    ///     .create_window()
+    ///     .border_width(2)
+    ///     .border_color(pixel)
+    ///     .build()
+    ///     .expect("could not create window");
+    /// ```
+    pub fn alloc_color(&self, color: RGB) -> Result<u64, ColorError> {
+        self.default_colormap().alloc_color(color)
+    }
+
+    /// Allocates a named color in the default colormap, see
+    /// `Colormap::alloc_named_color()`
+    pub fn alloc_named_color(&self, name: &str) -> Result<u64, ColorError> {
+        self.default_colormap().alloc_named_color(name)
+    }
+
+    /// # Examples
+    /// ```
+    /// use undici::{color::RGB, x11::display::Display};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// let steel_blue = display.lookup_named_color("steelblue").unwrap();
+    /// assert_eq!(steel_blue, RGB { r: 70, g: 130, b: 180 });
+    ///
+    /// assert!(display.lookup_named_color("not a color").is_err());
+    /// ```
+    pub fn lookup_named_color(&self, name: &str) -> Result<RGB, ColorError> {
+        self.default_colormap().lookup_named_color(name)
+    }
+
+    /// Gives back colors allocated in the default colormap
+    pub fn free_colors(&self, pixels: &[u64]) {
+        self.default_colormap().free_colors(pixels)
+    }
+
+    /// Creates a colormap for `visual` on the screen the visual belongs to, windows with a
+    /// visual other than the default one need one of these
+    pub fn create_colormap(&self, visual: &Visual) -> OwnedColormap<'_> {
+        let id = unsafe {
+            XCreateColormap(
+                self.display,
+                XRootWindow(self.display, visual.screen),
+                visual.visual,
+                AllocNone,
+            )
+        };
+
+        OwnedColormap {
+            colormap: Colormap {
+                id,
+                display: self.display,
+            },
+            display: self,
+        }
+    }
+}
+
+impl OwnedColormap<'_> {
+    /// A copy of the underlying `Colormap`, keep in mind that it stops being valid once the
+    /// `OwnedColormap` is dropped
+    pub fn colormap(&self) -> Colormap {
+        self.colormap
+    }
+}
+
+impl Deref for OwnedColormap<'_> {
+    type Target = Colormap;
+
+    fn deref(&self) -> &Colormap {
+        &self.colormap
+    }
+}
+
+impl Drop for OwnedColormap<'_> {
+    fn drop(&mut self) {
+        unsafe { XFreeColormap(self.display.display, self.colormap.id) };
+    }
+}
@@ -1,10 +1,45 @@
-use std::ffi::c_uint;
+use std::{
+    ffi::{c_long, c_uint},
+    ops::{BitOr, BitOrAssign},
+};
+use x11::xlib::{
+    ButtonMotionMask, ButtonPressMask, ButtonReleaseMask, ColormapChangeMask, EnterWindowMask,
+    ExposureMask, FocusChangeMask, KeyPressMask, KeyReleaseMask, KeymapStateMask, LeaveWindowMask,
+    NoEventMask, OwnerGrabButtonMask, PointerMotionMask, PropertyChangeMask, ResizeRedirectMask,
+    StructureNotifyMask, SubstructureNotifyMask, SubstructureRedirectMask, VisibilityChangeMask,
+};
 
-#[derive(PartialEq)]
+/// A mouse button. `From<c_uint>` is the canonical way to turn an X button number into
+/// one, it never gives `Other` for a button that has its own variant. An `Other` built by
+/// hand still compares equal to the named button with the same number, so
+/// `Other(8) == Back`, but `match`es only see the variant it was built as
+/// # Examples
+/// ```
+/// use undici::x11::common::MouseButton;
+///
+/// assert_eq!(MouseButton::from(8), MouseButton::Back);
+/// assert_eq!(MouseButton::from(12), MouseButton::Other(12));
+/// assert_eq!(MouseButton::Other(8), MouseButton::Back);
+/// ```
+#[derive(Debug, Clone, Copy)]
 pub enum MouseButton {
     Left,
     Middle,
     Right,
+
+    /// X reports every step of the wheel as a press and a release of these buttons
+    WheelUp,
+    WheelDown,
+    WheelLeft,
+    WheelRight,
+
+    /// The side buttons, usually used to go back and forward in browsers
+    Back,
+    Forward,
+
+    /// Any other button, by its X number, `MouseButton::from()` only uses it for buttons
+    /// that are not one of the above
+    Other(u8),
 }
 
 impl MouseButton {
@@ -13,6 +48,39 @@ impl MouseButton {
             MouseButton::Left => 1,
             MouseButton::Middle => 2,
             MouseButton::Right => 3,
+            MouseButton::WheelUp => 4,
+            MouseButton::WheelDown => 5,
+            MouseButton::WheelLeft => 6,
+            MouseButton::WheelRight => 7,
+            MouseButton::Back => 8,
+            MouseButton::Forward => 9,
+            MouseButton::Other(button) => *button as c_uint,
+        }
+    }
+}
+
+/// Buttons are compared by their X number, see `MouseButton`
+impl PartialEq for MouseButton {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_c_uint() == other.as_c_uint()
+    }
+}
+
+impl Eq for MouseButton {}
+
+impl From<c_uint> for MouseButton {
+    fn from(button: c_uint) -> Self {
+        match button {
+            1 => MouseButton::Left,
+            2 => MouseButton::Middle,
+            3 => MouseButton::Right,
+            4 => MouseButton::WheelUp,
+            5 => MouseButton::WheelDown,
+            6 => MouseButton::WheelLeft,
+            7 => MouseButton::WheelRight,
+            8 => MouseButton::Back,
+            9 => MouseButton::Forward,
+            other => MouseButton::Other(other as u8),
         }
     }
 }
@@ -28,3 +96,77 @@ impl<T> Vector2<T> {
         Self { x, y }
     }
 }
+
+/// A rectangle, `x` and `y` are its top left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Which events a window wants to receive, they can be combined with `|`
+/// # Examples
+/// ```
+/// use undici::x11::common::EventMask;
+///
+/// let mask = EventMask::KEY_PRESS | EventMask::BUTTON_PRESS;
+///
+/// assert!(mask.contains(EventMask::KEY_PRESS));
+/// assert!(!mask.contains(EventMask::EXPOSURE));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct EventMask(pub(crate) c_long);
+
+impl EventMask {
+    pub const NONE: Self = Self(NoEventMask);
+    pub const KEY_PRESS: Self = Self(KeyPressMask);
+    pub const KEY_RELEASE: Self = Self(KeyReleaseMask);
+    pub const BUTTON_PRESS: Self = Self(ButtonPressMask);
+    pub const BUTTON_RELEASE: Self = Self(ButtonReleaseMask);
+    pub const ENTER_WINDOW: Self = Self(EnterWindowMask);
+    pub const LEAVE_WINDOW: Self = Self(LeaveWindowMask);
+    pub const POINTER_MOTION: Self = Self(PointerMotionMask);
+    pub const BUTTON_MOTION: Self = Self(ButtonMotionMask);
+    pub const KEYMAP_STATE: Self = Self(KeymapStateMask);
+    pub const EXPOSURE: Self = Self(ExposureMask);
+    pub const VISIBILITY_CHANGE: Self = Self(VisibilityChangeMask);
+    pub const STRUCTURE_NOTIFY: Self = Self(StructureNotifyMask);
+    pub const RESIZE_REDIRECT: Self = Self(ResizeRedirectMask);
+    pub const SUBSTRUCTURE_NOTIFY: Self = Self(SubstructureNotifyMask);
+    pub const SUBSTRUCTURE_REDIRECT: Self = Self(SubstructureRedirectMask);
+    pub const FOCUS_CHANGE: Self = Self(FocusChangeMask);
+    pub const PROPERTY_CHANGE: Self = Self(PropertyChangeMask);
+    pub const COLORMAP_CHANGE: Self = Self(ColormapChangeMask);
+    pub const OWNER_GRAB_BUTTON: Self = Self(OwnerGrabButtonMask);
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for EventMask {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for EventMask {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
@@ -1,6 +1,62 @@
-use super::{common::Vector2, window::Window};
+use super::{common::Vector2, display::Display, window::Window};
 use x11::xlib::*;
 
+/// Some of the shapes of the X cursor font, the full list is in `X11/cursorfont.h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+    /// The usual arrow pointer
+    Arrow,
+    Crosshair,
+    Hand,
+
+    /// The I-beam used over text
+    Text,
+    Watch,
+
+    /// Four arrows, for moving things around
+    Move,
+
+    /// Any other shape of the cursor font, by its number
+    Other(u32),
+}
+
+impl CursorShape {
+    fn as_c_uint(&self) -> u32 {
+        match self {
+            CursorShape::Arrow => 68,
+            CursorShape::Crosshair => 34,
+            CursorShape::Hand => 60,
+            CursorShape::Text => 152,
+            CursorShape::Watch => 150,
+            CursorShape::Move => 52,
+            CursorShape::Other(shape) => *shape,
+        }
+    }
+}
+
+/// A cursor image, it can be shown during a pointer grab. Freed when dropped, so it
+/// can't outlive the `Display` it was created on
+pub struct Cursor<'a> {
+    pub(crate) id: u64,
+    display: &'a Display,
+}
+
+impl Display {
+    /// Creates a cursor from one of the shapes of the standard cursor font
+    pub fn create_font_cursor(&self, shape: CursorShape) -> Cursor<'_> {
+        Cursor {
+            id: unsafe { XCreateFontCursor(self.display, shape.as_c_uint()) },
+            display: self,
+        }
+    }
+}
+
+impl Drop for Cursor<'_> {
+    fn drop(&mut self) {
+        unsafe { XFreeCursor(self.display.display, self.id) };
+    }
+}
+
 impl Window {
     /// # Examples
     /// ```
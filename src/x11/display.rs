@@ -1,24 +1,142 @@
-use std::ptr::null;
+use std::{
+    cell::RefCell,
+    env,
+    ffi::CString,
+    io,
+    net::{TcpStream, ToSocketAddrs},
+    os::unix::{
+        io::{AsRawFd, RawFd},
+        net::UnixStream,
+    },
+    sync::Once,
+    time::Duration,
+};
 
-use super::{errors::DisplayError, window::Window};
-use x11::xlib::{XCloseDisplay, XDefaultRootWindow, XOpenDisplay, _XDisplay};
+use super::{
+    atom::AtomCache,
+    errors::{error_handler, take_error, DisplayError},
+    keyboard::ModifierMap,
+    window::Window,
+};
+use x11::xlib::{
+    _XDisplay, XCloseDisplay, XConnectionNumber, XDefaultRootWindow, XOpenDisplay, XSetErrorHandler,
+};
+
+/// Xlib's error handler is process wide, so we only need to install it once
+static INSTALL_ERROR_HANDLER: Once = Once::new();
+
+/// X servers listen on TCP port `6000 + display number`
+const X_TCP_PORT: u16 = 6000;
+
+/// How long we wait for a remote server while figuring out why we couldn't connect, a
+/// firewall that drops packets would otherwise keep us waiting for minutes
+const DIAGNOSE_TIMEOUT: Duration = Duration::from_secs(3);
 
 pub struct Display {
     pub(crate) display: *mut _XDisplay,
+    pub(crate) atom_cache: RefCell<AtomCache>,
 }
 
-fn new_display() -> Result<Display, DisplayError> {
-    let display = unsafe { XOpenDisplay(null()) };
+fn new_display(name: Option<&str>) -> Result<Display, DisplayError> {
+    INSTALL_ERROR_HANDLER.call_once(|| unsafe {
+        XSetErrorHandler(Some(error_handler));
+    });
+
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => match env::var("DISPLAY") {
+            Ok(name) if !name.is_empty() => name,
+            _ => return Err(DisplayError::NoDisplayVariable),
+        },
+    };
+
+    let name_c =
+        CString::new(name.as_str()).map_err(|_| DisplayError::InvalidName(name.clone()))?;
+    let display = unsafe { XOpenDisplay(name_c.as_ptr()) };
 
     if display.is_null() {
-        Err(DisplayError::CouldNotCreate)
+        Err(diagnose_failure(&name))
     } else {
-        Ok(Display { display })
+        Ok(Display {
+            display,
+            atom_cache: RefCell::default(),
+        })
     }
 }
 
+/// Xlib only tells us that it failed, so to know why we try to connect to the server
+/// ourselves: if we can't, the server is not there. If we can, we guess that it refused
+/// the handshake, which is by far the most common reason, but Xlib could also have failed
+/// for something else, like running out of memory
+fn diagnose_failure(name: &str) -> DisplayError {
+    // Display names look like `[protocol/][host]:number[.screen]`
+    let Some((host, number)) = name.rsplit_once(':') else {
+        return DisplayError::InvalidName(name.to_string());
+    };
+
+    let Some(number) = number.split('.').next().and_then(|n| n.parse::<u16>().ok()) else {
+        return DisplayError::InvalidName(name.to_string());
+    };
+
+    let (protocol, host) = match host.split_once('/') {
+        Some((protocol, host)) if !protocol.is_empty() => (protocol, host),
+        _ => ("", host),
+    };
+
+    let connection = if host.starts_with('/') {
+        // macOS' launchd gives out socket paths as display names
+        UnixStream::connect(host).map(drop)
+    } else if protocol == "tcp" || !(host.is_empty() || host == "unix" || protocol == "unix") {
+        connect_tcp(host, X_TCP_PORT.saturating_add(number))
+    } else {
+        connect_unix_socket(number)
+    };
+
+    match connection {
+        Ok(()) => DisplayError::AuthorizationFailed(name.to_string()),
+        Err(error) => DisplayError::ConnectionRefused {
+            name: name.to_string(),
+            reason: error.to_string(),
+        },
+    }
+}
+
+fn connect_tcp(host: &str, port: u16) -> io::Result<()> {
+    let mut result = Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("could not resolve \"{}\"", host),
+    ));
+
+    for address in (host, port).to_socket_addrs()? {
+        result = TcpStream::connect_timeout(&address, DIAGNOSE_TIMEOUT).map(drop);
+
+        if result.is_ok() {
+            break;
+        }
+    }
+
+    result
+}
+
+fn connect_unix_socket(number: u16) -> io::Result<()> {
+    let path = format!("/tmp/.X11-unix/X{}", number);
+
+    // On linux Xlib prefers the abstract socket, which also works when `/tmp` is not shared
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+
+        let address = SocketAddr::from_abstract_name(path.as_bytes())?;
+        if UnixStream::connect_addr(&address).is_ok() {
+            return Ok(());
+        }
+    }
+
+    UnixStream::connect(path).map(drop)
+}
+
 impl Display {
-    /// Connects to the Display.
+    /// Connects to the Display in the `DISPLAY` environment variable.
     /// # Examples
     /// ```
     /// use undici::x11::display::Display;
@@ -27,7 +145,19 @@ impl Display {
     ///     .expect("Hopefully there are no errors!");
     /// ```
     pub fn new() -> Result<Self, DisplayError> {
-        new_display()
+        new_display(None)
+    }
+
+    /// Connects to a specific Display, like `:0`, `:90` or `localhost:10.0`
+    /// # Examples
+    /// ```no_run
+    /// use undici::x11::display::Display;
+    ///
+    /// // For example a Xephyr window started with `Xephyr -br -ac -noreset :90`
+    /// let display = Display::open(":90").expect("is Xephyr running?");
+    /// ```
+    pub fn open(name: &str) -> Result<Self, DisplayError> {
+        new_display(Some(name))
     }
 
     /// The "screen" in X11 is just a window that contains everything, it's called the
@@ -54,8 +184,24 @@ impl Display {
     }
 }
 
+/// The file descriptor of the connection to the X server, it becomes readable when new
+/// events arrive, so it can be registered with epoll, mio, calloop...
+///
+/// Keep in mind that Xlib can read events into its own queue while doing other requests,
+/// so always drain `poll_event()` after it becomes readable, and before going to sleep
+impl AsRawFd for Display {
+    fn as_raw_fd(&self) -> RawFd {
+        unsafe { XConnectionNumber(self.display) }
+    }
+}
+
 impl Drop for Display {
     fn drop(&mut self) {
         unsafe { XCloseDisplay(self.display) };
+
+        // A new display could end up at the same address, it must not see our errors or
+        // our modifier map
+        take_error(self.display, 0);
+        ModifierMap::forget(self.display);
     }
 }
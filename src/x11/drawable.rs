@@ -0,0 +1,445 @@
+use super::{
+    common::{Rect, Vector2},
+    errors::{checked, checked_reply, ImageError, ProtocolError},
+    gc::GC,
+    image::Image,
+    pixmap::Pixmap,
+    window::{OwnedWindow, Window},
+};
+use x11::xlib::{
+    self, _XDisplay, CoordModeOrigin, XAllPlanes, XClearArea, XCopyArea, XDrawArc, XDrawLine,
+    XDrawLines, XDrawPoint, XDrawPoints, XDrawRectangle, XFillArc, XFillPolygon, XFillRectangle,
+    XGetGeometry, XGetImage, XPoint, ZPixmap,
+};
+
+mod sealed {
+    use x11::xlib::_XDisplay;
+
+    /// Keeps `Drawable` from being implemented outside of this crate, the raw parts are
+    /// only meaningful for X resources we created
+    pub trait Sealed {
+        fn raw_parts(&self) -> (*mut _XDisplay, u64);
+    }
+}
+
+/// X angles are in 1/64ths of a degree, and like coordinates they only have 16 bits
+fn to_x_angle(degrees: f32) -> i32 {
+    (degrees * 64.)
+        .round()
+        .clamp(i16::MIN as f32, i16::MAX as f32) as i32
+}
+
+/// The protocol only has 16 bits for coordinates, clamping keeps points that don't fit
+/// on the same side of the drawable, instead of wrapping them around
+fn clamp_coordinate(coordinate: i32) -> i16 {
+    coordinate.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+}
+
+fn to_xpoint(point: &Vector2<i32>) -> XPoint {
+    XPoint {
+        x: clamp_coordinate(point.x),
+        y: clamp_coordinate(point.y),
+    }
+}
+
+/// Like `to_xpoint()`, sizes are unsigned 16 bit numbers
+fn clamp_rect(rect: Rect) -> Rect {
+    Rect::new(
+        clamp_coordinate(rect.x).into(),
+        clamp_coordinate(rect.y).into(),
+        rect.width.min(u16::MAX.into()),
+        rect.height.min(u16::MAX.into()),
+    )
+}
+
+fn to_xpoints(points: &[Vector2<i32>]) -> Vec<XPoint> {
+    points.iter().map(to_xpoint).collect()
+}
+
+/// Something that can be drawn on and read from, either a `Window` or a `Pixmap`. The
+/// drawing methods and `copy_area()` clamp coordinates to what X can handle, from
+/// `-32768` to `32767`, and sizes to `65535`
+pub trait Drawable: sealed::Sealed {
+    /// The raw id of the window or pixmap
+    fn drawable_id(&self) -> u64 {
+        self.raw_parts().1
+    }
+
+    /// The size and depth of the drawable
+    fn try_get_geometry(&self) -> Result<(Vector2<u32>, u32), ProtocolError> {
+        let (display, id) = self.raw_parts();
+
+        let (mut root, mut x, mut y, mut width, mut height, mut border_width, mut depth) =
+            (0, 0, 0, 0, 0, 0, 0);
+
+        checked_reply(display, || unsafe {
+            XGetGeometry(
+                display,
+                id,
+                &mut root,
+                &mut x,
+                &mut y,
+                &mut width,
+                &mut height,
+                &mut border_width,
+                &mut depth,
+            )
+        })?;
+
+        Ok((Vector2::new(width, height), depth))
+    }
+
+    /// Takes a "picture" of the whole drawable
+    /// # Panics
+    /// If X can't give us the image, for example because a window is not viewable, use
+    /// `try_get_image()` to get an error instead
+    fn get_image(&self) -> Image {
+        let (scale, _) = self.try_get_geometry().unwrap_or((Vector2::new(0, 0), 0));
+
+        self.get_image_region(Rect::new(0, 0, scale.x, scale.y))
+    }
+
+    /// Like `get_image()`, but only of a part of the drawable, `region` has to be inside
+    /// of it
+    /// # Examples
+    /// Reading back what was drawn on a pixmap
+    /// ```
+    /// use undici::{
+    ///     color::RGB,
+    ///     x11::{
+    ///         common::{Rect, Vector2},
+    ///         display::Display,
+    ///         drawable::Drawable,
+    ///     },
+    /// };
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screen = display.default_screen();
+    ///
+    /// let pixmap = display
+    ///     .create_pixmap(Vector2::new(4, 4), screen.depth as u32)
+    ///     .expect("could not create pixmap");
+    ///
+    /// let red = RGB { r: 255, g: 0, b: 0 };
+    /// let gc = display.create_gc(&pixmap);
+    /// gc.set_foreground(display.alloc_color(red).unwrap());
+    /// pixmap.fill_rectangle(&gc, Rect::new(0, 0, 4, 4));
+    ///
+    /// let image = pixmap.get_image_region(Rect::new(0, 0, 4, 4));
+    /// assert_eq!(image.get_pixel(Vector2::new(2, 2)), red);
+    /// ```
+    /// # Panics
+    /// Same as `get_image()`, use `try_get_image_region()` to get an error instead
+    fn get_image_region(&self, region: Rect) -> Image {
+        let (display, id) = self.raw_parts();
+
+        let image = unsafe {
+            XGetImage(
+                display,
+                id,
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                XAllPlanes(),
+                ZPixmap,
+            )
+        };
+
+        assert!(!image.is_null(), "could not get the image of the drawable");
+
+        let mut image = unsafe { Image::from_ptr(image) };
+        image.fill_missing_masks(display);
+
+        image
+    }
+
+    /// Like `get_image`, but reports errors instead of handing out a broken `Image`, for
+    /// example a `BadMatch` when a window is not viewable
+    fn try_get_image(&self) -> Result<Image, ImageError> {
+        let (scale, _) = self.try_get_geometry()?;
+
+        self.try_get_image_region(Rect::new(0, 0, scale.x, scale.y))
+    }
+
+    /// Like `get_image_region`, but reports errors, for example a `BadMatch` when the
+    /// region goes outside of the drawable
+    fn try_get_image_region(&self, region: Rect) -> Result<Image, ImageError> {
+        let (display, id) = self.raw_parts();
+
+        let image = checked(display, || unsafe {
+            XGetImage(
+                display,
+                id,
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                XAllPlanes(),
+                ZPixmap,
+            )
+        })?;
+
+        let mut image = unsafe { Image::from_nullable_ptr(image)? };
+        image.fill_missing_masks(display);
+
+        Ok(image)
+    }
+
+    /// Copies `area` of `source` to `destination` in this drawable, both need to have
+    /// the same depth and be on the same screen. This is how a pixmap that was drawn
+    /// off screen is shown on a window
+    /// # Examples
+    /// Double buffering, drawing happens on a pixmap, and the window only ever gets the
+    /// finished frame
+    /// ```
+    /// use undici::x11::{
+    ///     common::{Rect, Vector2},
+    ///     display::Display,
+    ///     drawable::Drawable,
+    /// };
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screen = display.default_screen();
+    ///
+    /// let window = display
+    ///     .create_window()
+    ///     .scale(Vector2::new(300, 20))
+    ///     .build()
+    ///     .expect("could not create window");
+    ///
+    /// window.map();
+    ///
+    /// let buffer = display
+    ///     .create_pixmap(Vector2::new(300, 20), screen.depth as u32)
+    ///     .expect("could not create pixmap");
+    ///
+    /// let gc = display.create_gc(&buffer);
+    /// gc.set_foreground(screen.black_pixel);
+    /// buffer.fill_rectangle(&gc, Rect::new(0, 0, 300, 20));
+    /// gc.set_foreground(screen.white_pixel);
+    /// buffer.fill_rectangle(&gc, Rect::new(0, 0, 120, 20));
+    ///
+    /// window.copy_area(&gc, &buffer, Rect::new(0, 0, 300, 20), Vector2::new(0, 0));
+    /// ```
+    fn copy_area(&self, gc: &GC, source: &impl Drawable, area: Rect, destination: Vector2<i32>) {
+        let (display, id) = self.raw_parts();
+        let (area, destination) = (clamp_rect(area), to_xpoint(&destination));
+
+        unsafe {
+            XCopyArea(
+                display,
+                source.drawable_id(),
+                id,
+                gc.gc,
+                area.x,
+                area.y,
+                area.width,
+                area.height,
+                destination.x.into(),
+                destination.y.into(),
+            )
+        };
+    }
+
+    fn draw_point(&self, gc: &GC, point: Vector2<i32>) {
+        let (display, id) = self.raw_parts();
+
+        let point = to_xpoint(&point);
+
+        unsafe { XDrawPoint(display, id, gc.gc, point.x.into(), point.y.into()) };
+    }
+
+    fn draw_points(&self, gc: &GC, points: &[Vector2<i32>]) {
+        let (display, id) = self.raw_parts();
+        let mut xpoints = to_xpoints(points);
+
+        unsafe {
+            XDrawPoints(
+                display,
+                id,
+                gc.gc,
+                xpoints.as_mut_ptr(),
+                xpoints.len() as i32,
+                CoordModeOrigin,
+            )
+        };
+    }
+
+    fn draw_line(&self, gc: &GC, from: Vector2<i32>, to: Vector2<i32>) {
+        let (display, id) = self.raw_parts();
+
+        let (from, to) = (to_xpoint(&from), to_xpoint(&to));
+
+        unsafe {
+            XDrawLine(
+                display,
+                id,
+                gc.gc,
+                from.x.into(),
+                from.y.into(),
+                to.x.into(),
+                to.y.into(),
+            )
+        };
+    }
+
+    /// Connects every point to the next one, the joints are drawn properly, unlike with
+    /// separate `draw_line()` calls
+    fn draw_lines(&self, gc: &GC, points: &[Vector2<i32>]) {
+        let (display, id) = self.raw_parts();
+        let mut xpoints = to_xpoints(points);
+
+        unsafe {
+            XDrawLines(
+                display,
+                id,
+                gc.gc,
+                xpoints.as_mut_ptr(),
+                xpoints.len() as i32,
+                CoordModeOrigin,
+            )
+        };
+    }
+
+    /// Like `draw_lines()`, but also connects the last point to the first one
+    fn draw_polygon(&self, gc: &GC, points: &[Vector2<i32>]) {
+        let (display, id) = self.raw_parts();
+
+        let Some(first) = points.first() else {
+            return;
+        };
+
+        let mut closed = to_xpoints(points);
+        closed.push(to_xpoint(first));
+
+        unsafe {
+            XDrawLines(
+                display,
+                id,
+                gc.gc,
+                closed.as_mut_ptr(),
+                closed.len() as i32,
+                CoordModeOrigin,
+            )
+        };
+    }
+
+    fn fill_polygon(&self, gc: &GC, points: &[Vector2<i32>]) {
+        let (display, id) = self.raw_parts();
+        let mut xpoints = to_xpoints(points);
+
+        unsafe {
+            XFillPolygon(
+                display,
+                id,
+                gc.gc,
+                xpoints.as_mut_ptr(),
+                xpoints.len() as i32,
+                xlib::Complex,
+                CoordModeOrigin,
+            )
+        };
+    }
+
+    /// The outline is drawn on the edge of `rect`, so it ends up 1 pixel wider and
+    /// taller than the same `fill_rectangle()`
+    fn draw_rectangle(&self, gc: &GC, rect: Rect) {
+        let (display, id) = self.raw_parts();
+        let rect = clamp_rect(rect);
+
+        unsafe { XDrawRectangle(display, id, gc.gc, rect.x, rect.y, rect.width, rect.height) };
+    }
+
+    fn fill_rectangle(&self, gc: &GC, rect: Rect) {
+        let (display, id) = self.raw_parts();
+        let rect = clamp_rect(rect);
+
+        unsafe { XFillRectangle(display, id, gc.gc, rect.x, rect.y, rect.width, rect.height) };
+    }
+
+    /// Draws part of the ellipse that fits in `bounds`. Angles are in degrees, `0` is at
+    /// 3 o'clock and they go counterclockwise, so `draw_arc(gc, bounds, 0., 360.)` draws
+    /// the whole ellipse
+    fn draw_arc(&self, gc: &GC, bounds: Rect, start_angle: f32, extent: f32) {
+        let (display, id) = self.raw_parts();
+        let bounds = clamp_rect(bounds);
+
+        unsafe {
+            XDrawArc(
+                display,
+                id,
+                gc.gc,
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                to_x_angle(start_angle),
+                to_x_angle(extent),
+            )
+        };
+    }
+
+    /// Like `draw_arc()`, but filled, like a pie slice
+    fn fill_arc(&self, gc: &GC, bounds: Rect, start_angle: f32, extent: f32) {
+        let (display, id) = self.raw_parts();
+        let bounds = clamp_rect(bounds);
+
+        unsafe {
+            XFillArc(
+                display,
+                id,
+                gc.gc,
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                to_x_angle(start_angle),
+                to_x_angle(extent),
+            )
+        };
+    }
+}
+
+impl Window {
+    /// Fills `area` with the window's background. If `exposures` is `true` the cleared
+    /// area is also reported with `Expose` events, as if it was just uncovered. Pixmaps
+    /// have no background, so this only works on windows
+    pub fn clear_area(&self, area: Rect, exposures: bool) {
+        unsafe {
+            XClearArea(
+                self.display,
+                self.id,
+                area.x,
+                area.y,
+                area.width,
+                area.height,
+                exposures.into(),
+            )
+        };
+    }
+}
+
+impl sealed::Sealed for Window {
+    fn raw_parts(&self) -> (*mut _XDisplay, u64) {
+        (self.display, self.id)
+    }
+}
+
+impl Drawable for Window {}
+
+impl sealed::Sealed for OwnedWindow<'_> {
+    fn raw_parts(&self) -> (*mut _XDisplay, u64) {
+        self.window.raw_parts()
+    }
+}
+
+impl Drawable for OwnedWindow<'_> {}
+
+impl sealed::Sealed for Pixmap<'_> {
+    fn raw_parts(&self) -> (*mut _XDisplay, u64) {
+        (self.display.display, self.id)
+    }
+}
+
+impl Drawable for Pixmap<'_> {}
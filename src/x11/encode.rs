@@ -0,0 +1,281 @@
+use super::image::Image;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// The file formats an `Image` can be saved as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// The simplest format there is, uncompressed and understood by pretty much every
+    /// image tool, but not by browsers
+    Ppm,
+
+    /// Uncompressed, 24 bit
+    Bmp,
+
+    /// Compressed without losing anything, the best choice for screenshots
+    Png,
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Default zlib level, higher ones are a lot slower for very little gain on screenshots
+const PNG_COMPRESSION_LEVEL: u8 = 6;
+
+impl Image {
+    /// Saves the image to a file, replacing it if it already exists
+    /// # Examples
+    /// ```no_run
+    /// use undici::x11::{display::Display, encode::ImageFormat};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screenshot = display.get_root_window().get_image();
+    ///
+    /// screenshot
+    ///     .save("screenshot.png", ImageFormat::Png)
+    ///     .expect("could not save the screenshot");
+    /// ```
+    pub fn save(&self, path: impl AsRef<Path>, format: ImageFormat) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        self.write_to(&mut writer, format)?;
+        writer.flush()
+    }
+
+    /// Encodes the image into `writer`, for example to send it through a socket. BMP and
+    /// PNG can't store an image without pixels, so an image with a width or height of `0`
+    /// gives an `InvalidInput` error with those, PPM is fine with it
+    /// # Examples
+    /// ```
+    /// use undici::x11::{display::Display, encode::ImageFormat};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screenshot = display.get_root_window().get_image();
+    ///
+    /// let mut ppm = Vec::new();
+    /// screenshot.write_to(&mut ppm, ImageFormat::Ppm).unwrap();
+    ///
+    /// assert!(ppm.starts_with(b"P6"));
+    /// ```
+    pub fn write_to(&self, writer: &mut impl Write, format: ImageFormat) -> io::Result<()> {
+        if format != ImageFormat::Ppm && (self.width == 0 || self.height == 0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{:?} images can't be empty", format),
+            ));
+        }
+
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            ImageFormat::Bmp => self.write_bmp(writer),
+            ImageFormat::Png => self.write_png(writer),
+        }
+    }
+
+    fn write_ppm(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.to_rgb_buffer())
+    }
+
+    fn write_bmp(&self, writer: &mut impl Write) -> io::Result<()> {
+        const HEADERS_SIZE: u32 = 14 + 40;
+
+        // Every row is padded to 4 bytes
+        let row_size = (self.width * 3).div_ceil(4) * 4;
+        let data_size = row_size * self.height;
+
+        // File header
+        writer.write_all(b"BM")?;
+        writer.write_all(&(HEADERS_SIZE + data_size).to_le_bytes())?;
+        writer.write_all(&[0; 4])?;
+        writer.write_all(&HEADERS_SIZE.to_le_bytes())?;
+
+        // Info header, a negative height means the rows go from top to bottom
+        writer.write_all(&40u32.to_le_bytes())?;
+        writer.write_all(&(self.width as i32).to_le_bytes())?;
+        writer.write_all(&(-(self.height as i32)).to_le_bytes())?;
+        writer.write_all(&1u16.to_le_bytes())?;
+        writer.write_all(&24u16.to_le_bytes())?;
+        writer.write_all(&[0; 4])?; // No compression
+        writer.write_all(&data_size.to_le_bytes())?;
+        writer.write_all(&[0; 16])?; // Resolution and palette, none of them matter
+
+        let rgb = self.to_rgb_buffer();
+        let padding = [0; 3];
+
+        for row in rgb.chunks_exact(self.width as usize * 3) {
+            let bgr: Vec<u8> = row
+                .chunks_exact(3)
+                .flat_map(|pixel| [pixel[2], pixel[1], pixel[0]])
+                .collect();
+
+            writer.write_all(&bgr)?;
+            writer.write_all(&padding[..(row_size - self.width * 3) as usize])?;
+        }
+
+        Ok(())
+    }
+
+    fn write_png(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+
+        // 8 bits per channel, RGB, and the only compression, filter and interlace
+        // methods there are
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // Every row starts with the filter it uses, we don't filter
+        let rgb = self.to_rgb_buffer();
+        let mut rows = Vec::with_capacity(rgb.len() + self.height as usize);
+
+        for row in rgb.chunks_exact(self.width as usize * 3) {
+            rows.push(0);
+            rows.extend_from_slice(row);
+        }
+
+        let data = miniz_oxide::deflate::compress_to_vec_zlib(&rows, PNG_COMPRESSION_LEVEL);
+
+        writer.write_all(&PNG_SIGNATURE)?;
+        write_png_chunk(writer, b"IHDR", &header)?;
+        write_png_chunk(writer, b"IDAT", &data)?;
+        write_png_chunk(writer, b"IEND", &[])
+    }
+}
+
+fn write_png_chunk(writer: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+
+    // The checksum covers the kind too
+    let crc = !crc32(crc32(!0, kind), data);
+    writer.write_all(&crc.to_be_bytes())
+}
+
+/// Continues the CRC-32 `crc` with `data`, PNG uses the same one as zip and gzip
+fn crc32(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+#[cfg(feature = "image")]
+impl From<&Image> for image::RgbaImage {
+    fn from(image: &Image) -> Self {
+        let rgba = image
+            .pixels()
+            .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, u8::MAX])
+            .collect();
+
+        image::RgbaImage::from_raw(image.width, image.height, rgba)
+            .expect("the buffer has exactly one pixel per pixel of the image")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2x2 image, red and green on top, blue and white below
+    fn image() -> Image {
+        Image::from_rgb(2, 2, &[255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255])
+    }
+
+    fn encode(image: &Image, format: ImageFormat) -> io::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        image.write_to(&mut buffer, format)?;
+
+        Ok(buffer)
+    }
+
+    fn u32_le(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    fn u32_be(bytes: &[u8], at: usize) -> u32 {
+        u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = encode(&image(), ImageFormat::Ppm).unwrap();
+
+        assert_eq!(ppm, b"P6\n2 2\n255\n\xff\0\0\0\xff\0\0\0\xff\xff\xff\xff");
+    }
+
+    #[test]
+    fn bmp() {
+        let bmp = encode(&image(), ImageFormat::Bmp).unwrap();
+
+        // Rows of 6 bytes get padded to 8
+        assert_eq!(bmp.len(), 54 + 2 * 8);
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(u32_le(&bmp, 2), bmp.len() as u32);
+        assert_eq!(u32_le(&bmp, 10), 54);
+        assert_eq!(u32_le(&bmp, 18), 2);
+        assert_eq!(u32_le(&bmp, 22) as i32, -2);
+        assert_eq!(
+            &bmp[54..],
+            [0, 0, 255, 0, 255, 0, 0, 0, 255, 0, 0, 255, 255, 255, 0, 0]
+        );
+    }
+
+    #[test]
+    fn png() {
+        let png = encode(&image(), ImageFormat::Png).unwrap();
+        assert_eq!(png[..8], PNG_SIGNATURE);
+
+        let mut chunks = Vec::new();
+        let mut at = 8;
+
+        while at < png.len() {
+            let length = u32_be(&png, at) as usize;
+            let kind_and_data = &png[at + 4..at + 8 + length];
+
+            assert_eq!(
+                u32_be(&png, at + 8 + length),
+                !crc32(!0, kind_and_data),
+                "wrong checksum"
+            );
+
+            chunks.push((&kind_and_data[..4], &kind_and_data[4..]));
+            at += 12 + length;
+        }
+
+        let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+
+        let rows = miniz_oxide::inflate::decompress_to_vec_zlib(chunks[1].1).unwrap();
+        assert_eq!(rows, [0, 255, 0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(!crc32(!0, b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn empty_images() {
+        for (width, height) in [(0, 4), (4, 0), (0, 0)] {
+            let image = Image::from_rgb(width, height, &[]);
+
+            for format in [ImageFormat::Bmp, ImageFormat::Png] {
+                let error = encode(&image, format).unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            }
+
+            let ppm = encode(&image, ImageFormat::Ppm).unwrap();
+            assert_eq!(ppm, format!("P6\n{} {}\n255\n", width, height).as_bytes());
+        }
+    }
+}
@@ -1,12 +1,376 @@
+use super::atom::Atom;
 use core::fmt;
+use std::{ffi::c_int, sync::Mutex};
+use x11::xlib::{_XDisplay, XErrorEvent, XNextRequest, XSync};
 
-#[derive(Debug, Clone)]
+/// How many unclaimed errors we keep around per process before dropping the oldest ones,
+/// errors from calls that nobody checks would otherwise pile up forever
+const MAX_PENDING_ERRORS: usize = 256;
+
+/// Every error X sent us, together with the display it came from. Xlib only lets us
+/// install one handler per process, so this has to be global
+static PENDING_ERRORS: Mutex<Vec<(usize, ProtocolError)>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DisplayError {
+    /// No display name was given and the `DISPLAY` environment variable is not set
+    NoDisplayVariable,
+
+    /// The display name is not something like `:0`, `:1.0` or `host:0`
+    InvalidName(String),
+
+    /// There is no X server listening at that display, or it refused the connection
+    ConnectionRefused { name: String, reason: String },
+
+    /// Xlib could not open the display even though the X server accepted our connection,
+    /// so it most likely rejected us during the handshake, usually because of a missing or
+    /// wrong `~/.Xauthority` cookie. Xlib doesn't tell us why it failed, so this is a guess
+    AuthorizationFailed(String),
+
+    /// Xlib could not open the display and we could not figure out why
     CouldNotCreate,
 }
 
 impl fmt::Display for DisplayError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "could not create display")
+        match self {
+            DisplayError::NoDisplayVariable => {
+                write!(f, "could not create display, DISPLAY is not set")
+            }
+            DisplayError::InvalidName(name) => write!(f, "invalid display name \"{}\"", name),
+            DisplayError::ConnectionRefused { name, reason } => {
+                write!(f, "could not connect to display \"{}\": {}", name, reason)
+            }
+            DisplayError::AuthorizationFailed(name) => {
+                write!(
+                    f,
+                    "could not open display \"{}\", probably because it refused our authorization",
+                    name
+                )
+            }
+            DisplayError::CouldNotCreate => write!(f, "could not create display"),
+        }
+    }
+}
+
+impl std::error::Error for DisplayError {}
+
+/// The error codes defined by the core X protocol, extensions can define their own, those
+/// end up in `Other`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    BadRequest,
+    BadValue,
+    BadWindow,
+    BadPixmap,
+    BadAtom,
+    BadCursor,
+    BadFont,
+    BadMatch,
+    BadDrawable,
+    BadAccess,
+    BadAlloc,
+    BadColor,
+    BadGC,
+    BadIDChoice,
+    BadName,
+    BadLength,
+    BadImplementation,
+    Other(u8),
+}
+
+impl From<u8> for ErrorCode {
+    fn from(code: u8) -> Self {
+        match code {
+            1 => ErrorCode::BadRequest,
+            2 => ErrorCode::BadValue,
+            3 => ErrorCode::BadWindow,
+            4 => ErrorCode::BadPixmap,
+            5 => ErrorCode::BadAtom,
+            6 => ErrorCode::BadCursor,
+            7 => ErrorCode::BadFont,
+            8 => ErrorCode::BadMatch,
+            9 => ErrorCode::BadDrawable,
+            10 => ErrorCode::BadAccess,
+            11 => ErrorCode::BadAlloc,
+            12 => ErrorCode::BadColor,
+            13 => ErrorCode::BadGC,
+            14 => ErrorCode::BadIDChoice,
+            15 => ErrorCode::BadName,
+            16 => ErrorCode::BadLength,
+            17 => ErrorCode::BadImplementation,
+            other => ErrorCode::Other(other),
+        }
+    }
+}
+
+/// An error the X server sent back in response to one of our requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolError {
+    pub code: ErrorCode,
+
+    /// The major opcode of the request that failed, for example `12` is `ConfigureWindow`
+    pub request_code: u8,
+    pub minor_code: u8,
+
+    /// The window, pixmap, atom... that the request was about
+    pub resource_id: u64,
+    pub serial: u64,
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "X protocol error {:?} (request {}.{}, resource {:#x}, serial {})",
+            self.code, self.request_code, self.minor_code, self.resource_id, self.serial
+        )
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+impl From<&XErrorEvent> for ProtocolError {
+    fn from(xerror: &XErrorEvent) -> Self {
+        Self {
+            code: xerror.error_code.into(),
+            request_code: xerror.request_code,
+            minor_code: xerror.minor_code,
+            resource_id: xerror.resourceid,
+            serial: xerror.serial,
+        }
+    }
+}
+
+/// Can be returned by the property methods of `Window`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyError {
+    Protocol(ProtocolError),
+
+    /// The property exists, but has a different type than the one we asked for
+    TypeMismatch {
+        expected: Atom,
+        found: Atom,
+    },
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropertyError::Protocol(error) => error.fmt(f),
+            PropertyError::TypeMismatch { expected, found } => write!(
+                f,
+                "expected a property of type {}, found one of type {}",
+                expected.id(),
+                found.id()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+impl From<ProtocolError> for PropertyError {
+    fn from(error: ProtocolError) -> Self {
+        PropertyError::Protocol(error)
+    }
+}
+
+/// Can be returned by the methods of `Window` that take a key name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    /// There is no keysym with this name, the names are the ones `xev` shows
+    UnknownKey(String),
+
+    /// The keysym exists, but no key of the current keyboard layout produces it
+    NotOnKeyboard(String),
+    Protocol(ProtocolError),
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyError::UnknownKey(name) => write!(f, "unknown key `{}`", name),
+            KeyError::NotOnKeyboard(name) => write!(f, "no key produces `{}`", name),
+            KeyError::Protocol(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl From<ProtocolError> for KeyError {
+    fn from(error: ProtocolError) -> Self {
+        KeyError::Protocol(error)
+    }
+}
+
+/// Can be returned by the methods that read images from the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    /// The server sent the image, but Xlib couldn't read or store it, usually because it
+    /// ran out of memory
+    NullImage,
+    Protocol(ProtocolError),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::NullImage => write!(f, "Xlib could not store the image"),
+            ImageError::Protocol(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<ProtocolError> for ImageError {
+    fn from(error: ProtocolError) -> Self {
+        ImageError::Protocol(error)
+    }
+}
+
+/// Why `Display`'s `grab_keyboard()` or `grab_pointer()` failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrabError {
+    /// Another client has an active grab on the keyboard or pointer
+    AlreadyGrabbed,
+
+    /// The grab is older than the last grab or focus change, this shouldn't happen since
+    /// we always grab with the current time
+    InvalidTime,
+
+    /// The window (or the one the pointer is confined to) is not viewable
+    NotViewable,
+
+    /// Another client froze the keyboard or pointer with a synchronous grab
+    Frozen,
+
+    /// A status the protocol doesn't define, only a broken server would send this
+    Unknown(i32),
+    Protocol(ProtocolError),
+}
+
+impl fmt::Display for GrabError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GrabError::AlreadyGrabbed => write!(f, "another client already has a grab"),
+            GrabError::InvalidTime => write!(f, "the grab is older than the last one"),
+            GrabError::NotViewable => write!(f, "the grab window is not viewable"),
+            GrabError::Frozen => write!(f, "another client froze the device"),
+            GrabError::Unknown(status) => write!(f, "unknown grab status {}", status),
+            GrabError::Protocol(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GrabError {}
+
+impl From<ProtocolError> for GrabError {
+    fn from(error: ProtocolError) -> Self {
+        GrabError::Protocol(error)
+    }
+}
+
+/// Can be returned by the color allocation methods of `Display` and `Colormap`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    /// Not a color name from the X color database, nor a valid `#rrggbb` string
+    UnknownName(String),
+
+    /// The colormap is full, this can only happen on visuals with few colors, like 8 bit
+    /// `PseudoColor` ones
+    CouldNotAllocate,
+    Protocol(ProtocolError),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorError::UnknownName(name) => write!(f, "unknown color `{}`", name),
+            ColorError::CouldNotAllocate => write!(f, "the colormap is full"),
+            ColorError::Protocol(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+impl From<ProtocolError> for ColorError {
+    fn from(error: ProtocolError) -> Self {
+        ColorError::Protocol(error)
+    }
+}
+
+/// Replaces Xlib's default handler, which prints the error and kills the whole process,
+/// with one that just stores the error so the `try_` methods can pick it up later
+pub(crate) unsafe extern "C" fn error_handler(
+    display: *mut _XDisplay,
+    xerror: *mut XErrorEvent,
+) -> c_int {
+    // Panicking here would unwind into C, so we just give up on a poisoned lock
+    if let (Some(xerror), Ok(mut errors)) = (xerror.as_ref(), PENDING_ERRORS.lock()) {
+        if errors.len() >= MAX_PENDING_ERRORS {
+            errors.remove(0);
+        }
+
+        errors.push((display as usize, xerror.into()));
+    }
+
+    0
+}
+
+/// Removes every stored error of `display`, returning the first one that happened at or
+/// after the request with serial `since`
+pub(crate) fn take_error(display: *mut _XDisplay, since: u64) -> Option<ProtocolError> {
+    let mut errors = PENDING_ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut first_error = None;
+
+    errors.retain(|(error_display, error)| {
+        if *error_display != display as usize {
+            return true;
+        }
+
+        if first_error.is_none() && error.serial >= since {
+            first_error = Some(error.clone());
+        }
+
+        false
+    });
+
+    first_error
+}
+
+/// Runs `request` and waits for the X server to process it, if it failed the error is
+/// returned instead of the request's value. This needs a round trip to the server, so it
+/// is slower than just firing the request
+pub(crate) fn checked<T>(
+    display: *mut _XDisplay,
+    request: impl FnOnce() -> T,
+) -> Result<T, ProtocolError> {
+    let since = unsafe { XNextRequest(display) };
+    let value = request();
+    unsafe { XSync(display, false.into()) };
+
+    match take_error(display, since) {
+        Some(error) => Err(error),
+        None => Ok(value),
+    }
+}
+
+/// Like `checked`, but for requests that wait for a reply from the server (like
+/// `XInternAtom`), by the time those return their error is already here, so we can skip
+/// the extra round trip
+pub(crate) fn checked_reply<T>(
+    display: *mut _XDisplay,
+    request: impl FnOnce() -> T,
+) -> Result<T, ProtocolError> {
+    let since = unsafe { XNextRequest(display) };
+    let value = request();
+
+    match take_error(display, since) {
+        Some(error) => Err(error),
+        None => Ok(value),
     }
 }
@@ -1,26 +1,40 @@
-use crate::x11::common::{MouseButton, Vector2};
+use crate::x11::{
+    common::{MouseButton, Vector2},
+    keyboard::ModifierMap,
+    window::Modifiers,
+};
 use x11::xlib::{XButtonEvent, XEvent};
 
 #[derive(PartialEq)]
 pub struct MouseEventData {
     pub button: MouseButton,
+
+    /// Relative to the window that got the event
+    pub position: Vector2<i32>,
     pub root_position: Vector2<i32>,
+
+    /// The modifiers that were held when the button was pressed
+    pub modifiers: Modifiers,
+
+    /// In milliseconds, from when the X server started
+    pub time: u64,
+
+    /// Whether the pointer is on the same screen as the window that got the event, if it
+    /// isn't `position` is always `0, 0`
+    pub same_screen: bool,
 }
 
 impl From<XEvent> for MouseEventData {
     fn from(xevent: XEvent) -> Self {
         let xbutton: XButtonEvent = xevent.into();
 
-        let button = match xbutton.button {
-            1 => MouseButton::Left,
-            2 => MouseButton::Middle,
-            3 => MouseButton::Right,
-            _ => unimplemented!(),
-        };
-
         Self {
-            button,
+            button: xbutton.button.into(),
+            position: Vector2::new(xbutton.x, xbutton.y),
             root_position: Vector2::new(xbutton.x_root, xbutton.y_root),
+            modifiers: ModifierMap::get(xbutton.display).modifiers_of(xbutton.state),
+            time: xbutton.time,
+            same_screen: xbutton.same_screen != 0,
         }
     }
 }
@@ -0,0 +1,84 @@
+use crate::x11::{errors::ProtocolError, window::Window};
+use x11::xlib::{PlaceOnTop, XCirculateEvent, XCirculateRequestEvent, XEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+    OnTop,
+    OnBottom,
+}
+
+/// A window asked to be raised or lowered by circulating its parent's children, this only
+/// arrives to the window that owns the substructure redirection of its parent
+#[derive(PartialEq)]
+pub struct CirculateRequestData {
+    pub window: Window,
+    pub parent_window: Window,
+    pub place: Place,
+}
+
+/// A window was raised or lowered by circulating its parent's children
+#[derive(PartialEq)]
+pub struct CirculateData {
+    pub window: Window,
+
+    /// The window that got the event, either `window` or its parent
+    pub event_window: Window,
+    pub place: Place,
+}
+
+impl CirculateRequestData {
+    /// Raises or lowers the window like it asked
+    pub fn grant(&self) -> Result<(), ProtocolError> {
+        match self.place {
+            Place::OnTop => self.window.try_raise(),
+            Place::OnBottom => self.window.try_lower(),
+        }
+    }
+
+    /// Leaves the stacking order as it is
+    pub fn deny(&self) {}
+}
+
+impl From<XEvent> for CirculateRequestData {
+    #[allow(non_upper_case_globals)]
+    fn from(xevent: XEvent) -> Self {
+        let xcirculate: XCirculateRequestEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xcirculate.window,
+                display: xcirculate.display,
+            },
+            parent_window: Window {
+                id: xcirculate.parent,
+                display: xcirculate.display,
+            },
+            place: match xcirculate.place {
+                PlaceOnTop => Place::OnTop,
+                _ => Place::OnBottom,
+            },
+        }
+    }
+}
+
+impl From<XEvent> for CirculateData {
+    #[allow(non_upper_case_globals)]
+    fn from(xevent: XEvent) -> Self {
+        let xcirculate: XCirculateEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xcirculate.window,
+                display: xcirculate.display,
+            },
+            event_window: Window {
+                id: xcirculate.event,
+                display: xcirculate.display,
+            },
+            place: match xcirculate.place {
+                PlaceOnTop => Place::OnTop,
+                _ => Place::OnBottom,
+            },
+        }
+    }
+}
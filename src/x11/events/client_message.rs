@@ -0,0 +1,64 @@
+use crate::x11::{atom::Atom, window::Window};
+use x11::xlib::{XClientMessageEvent, XEvent};
+
+/// The data of a client message, its format tells how the 20 bytes should be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientMessageContent {
+    Format8([u8; 20]),
+    Format16([u16; 10]),
+    Format32([u32; 5]),
+}
+
+/// A message another client sent us, this is how EWMH requests (like `_NET_WM_STATE`) and
+/// `WM_DELETE_WINDOW` arrive
+#[derive(PartialEq)]
+pub struct ClientMessageData {
+    pub window: Window,
+    pub message_type: Atom,
+    pub data: ClientMessageContent,
+}
+
+impl From<XEvent> for ClientMessageData {
+    fn from(xevent: XEvent) -> Self {
+        let xclient: XClientMessageEvent = xevent.into();
+
+        let data = match xclient.format {
+            8 => {
+                let mut bytes = [0u8; 20];
+                for (byte, value) in bytes.iter_mut().zip(xclient.data.as_bytes()) {
+                    *byte = *value as u8;
+                }
+
+                ClientMessageContent::Format8(bytes)
+            }
+
+            16 => {
+                let mut shorts = [0u16; 10];
+                for (short, value) in shorts.iter_mut().zip(xclient.data.as_shorts()) {
+                    *short = *value as u16;
+                }
+
+                ClientMessageContent::Format16(shorts)
+            }
+
+            // Xlib stores 32 bit values in `long`s, which are 64 bits on most platforms
+            _ => {
+                let mut longs = [0u32; 5];
+                for (long, value) in longs.iter_mut().zip(xclient.data.as_longs()) {
+                    *long = *value as u32;
+                }
+
+                ClientMessageContent::Format32(longs)
+            }
+        };
+
+        Self {
+            window: Window {
+                id: xclient.window,
+                display: xclient.display,
+            },
+            message_type: Atom(xclient.message_type),
+            data,
+        }
+    }
+}
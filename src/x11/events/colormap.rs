@@ -0,0 +1,35 @@
+use crate::x11::{colormap::Colormap, window::Window};
+use x11::xlib::{ColormapInstalled, XColormapEvent, XEvent};
+
+/// The colormap of a window was changed, or it was installed or uninstalled
+#[derive(PartialEq)]
+pub struct ColormapData {
+    pub window: Window,
+
+    /// `None` if the colormap was freed
+    pub colormap: Option<Colormap>,
+
+    /// `true` if the window got a different colormap, `false` if its colormap was just
+    /// installed or uninstalled
+    pub new: bool,
+    pub installed: bool,
+}
+
+impl From<XEvent> for ColormapData {
+    fn from(xevent: XEvent) -> Self {
+        let xcolormap: XColormapEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xcolormap.window,
+                display: xcolormap.display,
+            },
+            colormap: (xcolormap.colormap != 0).then_some(Colormap {
+                id: xcolormap.colormap,
+                display: xcolormap.display,
+            }),
+            new: xcolormap.new != 0,
+            installed: xcolormap.state == ColormapInstalled,
+        }
+    }
+}
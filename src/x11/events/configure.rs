@@ -0,0 +1,165 @@
+use crate::x11::{
+    common::Vector2,
+    errors::{checked, ProtocolError},
+    window::{StackMode, Window, WindowChanges},
+};
+use x11::xlib::{
+    CWBorderWidth, CWHeight, CWSibling, CWStackMode, CWWidth, ConfigureNotify, StructureNotifyMask,
+    XConfigureEvent, XConfigureRequestEvent, XEvent, XSendEvent, XTranslateCoordinates, CWX, CWY,
+};
+
+/// A window asked to be moved, resized or restacked, this only arrives to the window that
+/// owns the substructure redirection of its parent (the window manager)
+#[derive(PartialEq)]
+pub struct ConfigureRequestData {
+    pub window: Window,
+    pub parent_window: Window,
+
+    /// Only the fields the window asked to change are `Some`
+    pub changes: WindowChanges,
+}
+
+/// A window was moved, resized or restacked
+#[derive(PartialEq)]
+pub struct ConfigureData {
+    pub window: Window,
+
+    /// The window that got the event, either `window` or its parent
+    pub event_window: Window,
+    pub position: Vector2<i32>,
+    pub scale: Vector2<i32>,
+    pub border_width: i32,
+
+    /// The sibling window this window is right on top of, `None` if it's at the bottom
+    pub above: Option<Window>,
+    pub override_redirect: bool,
+}
+
+impl ConfigureRequestData {
+    /// Applies the changes exactly as the window asked
+    pub fn grant(&self) -> Result<(), ProtocolError> {
+        self.window.try_configure(&self.changes)
+    }
+
+    /// Applies `changes` instead of what the window asked, for example to keep a tiled
+    /// window in its tile. The window is told about the final geometry with a
+    /// `ConfigureNotify` event, like it would with `grant()`
+    pub fn grant_with(&self, changes: &WindowChanges) -> Result<(), ProtocolError> {
+        self.window.try_configure(changes)?;
+
+        // If the geometry ends up being the same X sends nothing, but the client is still
+        // waiting for an answer
+        self.send_current_geometry()
+    }
+
+    /// Leaves the window as it is. The client still expects an answer, so, like ICCCM
+    /// says, we send it a fake `ConfigureNotify` with its current geometry
+    pub fn deny(&self) -> Result<(), ProtocolError> {
+        self.send_current_geometry()
+    }
+
+    fn send_current_geometry(&self) -> Result<(), ProtocolError> {
+        let attributes = self.window.try_get_attributes()?;
+        let display = self.window.display;
+
+        // ICCCM wants root coordinates, which differ from the position if the window was
+        // reparented into a frame
+        let (mut root_x, mut root_y, mut child) = (0, 0, 0);
+        unsafe {
+            XTranslateCoordinates(
+                display,
+                self.window.id,
+                attributes.root,
+                0,
+                0,
+                &mut root_x,
+                &mut root_y,
+                &mut child,
+            )
+        };
+
+        let mut xconfigure: XConfigureEvent = unsafe { std::mem::zeroed() };
+        xconfigure.type_ = ConfigureNotify;
+        xconfigure.display = display;
+        xconfigure.event = self.window.id;
+        xconfigure.window = self.window.id;
+        xconfigure.x = root_x - attributes.border_width;
+        xconfigure.y = root_y - attributes.border_width;
+        xconfigure.width = attributes.width;
+        xconfigure.height = attributes.height;
+        xconfigure.border_width = attributes.border_width;
+
+        let mut xevent: XEvent = xconfigure.into();
+
+        checked(display, || unsafe {
+            XSendEvent(
+                display,
+                self.window.id,
+                false.into(),
+                StructureNotifyMask,
+                &mut xevent,
+            )
+        })?;
+
+        Ok(())
+    }
+}
+
+impl From<XEvent> for ConfigureRequestData {
+    fn from(xevent: XEvent) -> Self {
+        let xconfigure: XConfigureRequestEvent = xevent.into();
+        let mask = xconfigure.value_mask as u16;
+
+        // The fields that are not in the mask contain garbage, so we throw them away
+        let only_if = |flag: u16, value: i32| (mask & flag != 0).then_some(value);
+
+        let changes = WindowChanges {
+            x: only_if(CWX, xconfigure.x),
+            y: only_if(CWY, xconfigure.y),
+            width: only_if(CWWidth, xconfigure.width).map(|width| width as u32),
+            height: only_if(CWHeight, xconfigure.height).map(|height| height as u32),
+            border_width: only_if(CWBorderWidth, xconfigure.border_width)
+                .map(|border_width| border_width as u32),
+            sibling: if mask & CWSibling != 0 {
+                Window::optional(xconfigure.display, xconfigure.above)
+            } else {
+                None
+            },
+            stack_mode: only_if(CWStackMode, xconfigure.detail).map(StackMode::from),
+        };
+
+        Self {
+            window: Window {
+                id: xconfigure.window,
+                display: xconfigure.display,
+            },
+            parent_window: Window {
+                id: xconfigure.parent,
+                display: xconfigure.display,
+            },
+            changes,
+        }
+    }
+}
+
+impl From<XEvent> for ConfigureData {
+    fn from(xevent: XEvent) -> Self {
+        let xconfigure: XConfigureEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xconfigure.window,
+                display: xconfigure.display,
+            },
+            event_window: Window {
+                id: xconfigure.event,
+                display: xconfigure.display,
+            },
+            position: Vector2::new(xconfigure.x, xconfigure.y),
+            scale: Vector2::new(xconfigure.width, xconfigure.height),
+            border_width: xconfigure.border_width,
+            above: Window::optional(xconfigure.display, xconfigure.above),
+            override_redirect: xconfigure.override_redirect != 0,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::x11::{common::Vector2, window::Window};
+use std::ffi::c_int;
+use x11::xlib::{
+    NotifyAncestor, NotifyGrab, NotifyInferior, NotifyNonlinear, NotifyNonlinearVirtual,
+    NotifyPointer, NotifyPointerRoot, NotifyUngrab, NotifyVirtual, NotifyWhileGrabbed,
+    XCrossingEvent, XEvent, XFocusChangeEvent,
+};
+
+/// Why the pointer or the focus moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyMode {
+    Normal,
+
+    /// Because a grab started
+    Grab,
+
+    /// Because a grab ended
+    Ungrab,
+
+    /// The focus moved while the keyboard was grabbed
+    WhileGrabbed,
+}
+
+/// Where the pointer or the focus moved, relative to the window that got the event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyDetail {
+    Ancestor,
+    Virtual,
+    Inferior,
+    Nonlinear,
+    NonlinearVirtual,
+    Pointer,
+    PointerRoot,
+    None,
+}
+
+/// The pointer entered or left a window
+#[derive(PartialEq)]
+pub struct CrossingData {
+    pub window: Window,
+    pub root_position: Vector2<i32>,
+
+    /// Relative to `window`
+    pub position: Vector2<i32>,
+    pub mode: NotifyMode,
+    pub detail: NotifyDetail,
+
+    /// If `window` is (or is inside of) the focused window
+    pub focus: bool,
+    pub same_screen: bool,
+    pub time: u64,
+}
+
+/// A window got or lost the keyboard focus
+#[derive(PartialEq)]
+pub struct FocusData {
+    pub window: Window,
+    pub mode: NotifyMode,
+    pub detail: NotifyDetail,
+}
+
+impl From<c_int> for NotifyMode {
+    #[allow(non_upper_case_globals)]
+    fn from(mode: c_int) -> Self {
+        match mode {
+            NotifyGrab => NotifyMode::Grab,
+            NotifyUngrab => NotifyMode::Ungrab,
+            NotifyWhileGrabbed => NotifyMode::WhileGrabbed,
+            _ => NotifyMode::Normal,
+        }
+    }
+}
+
+impl From<c_int> for NotifyDetail {
+    #[allow(non_upper_case_globals)]
+    fn from(detail: c_int) -> Self {
+        match detail {
+            NotifyAncestor => NotifyDetail::Ancestor,
+            NotifyVirtual => NotifyDetail::Virtual,
+            NotifyInferior => NotifyDetail::Inferior,
+            NotifyNonlinear => NotifyDetail::Nonlinear,
+            NotifyNonlinearVirtual => NotifyDetail::NonlinearVirtual,
+            NotifyPointer => NotifyDetail::Pointer,
+            NotifyPointerRoot => NotifyDetail::PointerRoot,
+            _ => NotifyDetail::None,
+        }
+    }
+}
+
+impl From<XEvent> for CrossingData {
+    fn from(xevent: XEvent) -> Self {
+        let xcrossing: XCrossingEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xcrossing.window,
+                display: xcrossing.display,
+            },
+            root_position: Vector2::new(xcrossing.x_root, xcrossing.y_root),
+            position: Vector2::new(xcrossing.x, xcrossing.y),
+            mode: xcrossing.mode.into(),
+            detail: xcrossing.detail.into(),
+            focus: xcrossing.focus != 0,
+            same_screen: xcrossing.same_screen != 0,
+            time: xcrossing.time,
+        }
+    }
+}
+
+impl From<XEvent> for FocusData {
+    fn from(xevent: XEvent) -> Self {
+        let xfocus: XFocusChangeEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xfocus.window,
+                display: xfocus.display,
+            },
+            mode: xfocus.mode.into(),
+            detail: xfocus.detail.into(),
+        }
+    }
+}
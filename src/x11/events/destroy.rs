@@ -0,0 +1,28 @@
+use crate::x11::window::Window;
+use x11::xlib::{XDestroyWindowEvent, XEvent};
+
+/// A window was destroyed, calling methods on `window` will fail from now on
+#[derive(PartialEq)]
+pub struct DestroyData {
+    pub window: Window,
+
+    /// The window that got the event, either `window` or its parent
+    pub event_window: Window,
+}
+
+impl From<XEvent> for DestroyData {
+    fn from(xevent: XEvent) -> Self {
+        let xdestroy: XDestroyWindowEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xdestroy.window,
+                display: xdestroy.display,
+            },
+            event_window: Window {
+                id: xdestroy.event,
+                display: xdestroy.display,
+            },
+        }
+    }
+}
@@ -1,15 +1,36 @@
 use super::{
     button::MouseEventData,
+    circulate::{CirculateData, CirculateRequestData},
+    client_message::ClientMessageData,
+    colormap::ColormapData,
+    configure::{ConfigureData, ConfigureRequestData},
     create::{xevent_to_window_create_data, WindowCreateData},
+    crossing::{CrossingData, FocusData},
+    destroy::DestroyData,
+    expose::{ExposeData, GraphicsExposeData, NoExposeData},
+    gravity::GravityData,
     key::KeyEventData,
+    keymap::KeymapData,
+    map::{MapData, MapRequestData, UnmapData},
+    mapping::{update_mappings, MappingData},
     motion::MotionData,
+    property::PropertyData,
+    reparent::ReparentData,
+    resize::ResizeRequestData,
+    selection::{SelectionClearData, SelectionNotifyData, SelectionRequestData},
+    visibility::VisibilityData,
 };
 use crate::x11::{
-    common::MouseButton,
+    common::{EventMask, MouseButton},
     display::Display,
-    window::{Modifier, Window},
+    errors::{checked, KeyError, ProtocolError},
+    keyboard::{Keysym, ModifierMap},
+    window::{Modifiers, Window},
+};
+use std::{
+    io,
+    time::{Duration, Instant},
 };
-use std::ffi::{c_char, c_uint, CString};
 use x11::xlib::*;
 
 #[derive(PartialEq)]
@@ -19,10 +40,45 @@ pub enum EventType {
     MouseButtonPress(MouseEventData),
     MouseButtonRelease(MouseEventData),
     MotionNotify(MotionData),
+    EnterNotify(CrossingData),
+    LeaveNotify(CrossingData),
+    FocusIn(FocusData),
+    FocusOut(FocusData),
+    KeymapNotify(KeymapData),
+    Expose(ExposeData),
+    GraphicsExpose(GraphicsExposeData),
+    NoExpose(NoExposeData),
+    VisibilityNotify(VisibilityData),
 
     /// This will only work if you called the `Window.grab_children_substucture` function
     WindowCreated(WindowCreateData),
 
+    DestroyNotify(DestroyData),
+    UnmapNotify(UnmapData),
+    MapNotify(MapData),
+    ReparentNotify(ReparentData),
+    ConfigureNotify(ConfigureData),
+    GravityNotify(GravityData),
+    CirculateNotify(CirculateData),
+
+    /// These are only sent to the client that redirects the substructure of the parent
+    /// window, in other words the window manager
+    MapRequest(MapRequestData),
+    ConfigureRequest(ConfigureRequestData),
+    CirculateRequest(CirculateRequestData),
+
+    /// Only sent to the client that selected `EventMask::RESIZE_REDIRECT` on the window
+    ResizeRequest(ResizeRequestData),
+
+    PropertyNotify(PropertyData),
+    SelectionClear(SelectionClearData),
+    SelectionRequest(SelectionRequestData),
+    SelectionNotify(SelectionNotifyData),
+    ColormapNotify(ColormapData),
+    ClientMessage(ClientMessageData),
+    MappingNotify(MappingData),
+
+    /// Events from extensions, which don't have a variant of their own
     Unimplemented,
 }
 
@@ -44,27 +100,60 @@ impl Event {
 
     #[allow(non_upper_case_globals)]
     pub(crate) fn get_data(&self) -> EventData {
-        let subwindow = unsafe {
-            if self.event.key.subwindow == 0 {
-                None
-            } else {
-                Some(Window {
-                    id: self.event.key.subwindow,
-                    display: self.event.key.display,
-                })
-            }
+        let event_type = unsafe { self.event.type_ };
+
+        // Only these events have a subwindow, for all the others that part of the union
+        // contains something else
+        let subwindow = match event_type {
+            KeyPress | KeyRelease | ButtonPress | ButtonRelease | MotionNotify => unsafe {
+                Window::optional(self.event.key.display, self.event.key.subwindow)
+            },
+
+            EnterNotify | LeaveNotify => unsafe {
+                Window::optional(self.event.crossing.display, self.event.crossing.subwindow)
+            },
+
+            _ => None,
         };
 
-        let type_ = match unsafe { self.event.type_ } {
+        let type_ = match event_type {
             KeyPress => EventType::KeyPress(self.event.into()),
             KeyRelease => EventType::KeyRelease(self.event.into()),
             ButtonPress => EventType::MouseButtonPress(self.event.into()),
             ButtonRelease => EventType::MouseButtonRelease(self.event.into()),
             MotionNotify => EventType::MotionNotify(self.event.into()),
+            EnterNotify => EventType::EnterNotify(self.event.into()),
+            LeaveNotify => EventType::LeaveNotify(self.event.into()),
+            FocusIn => EventType::FocusIn(self.event.into()),
+            FocusOut => EventType::FocusOut(self.event.into()),
+            KeymapNotify => EventType::KeymapNotify(self.event.into()),
+            Expose => EventType::Expose(self.event.into()),
+            GraphicsExpose => EventType::GraphicsExpose(self.event.into()),
+            NoExpose => EventType::NoExpose(self.event.into()),
+            VisibilityNotify => EventType::VisibilityNotify(self.event.into()),
 
             // Check `xevent_to_window_create_data()` comment for why this is here
             CreateNotify => xevent_to_window_create_data(self.event),
 
+            DestroyNotify => EventType::DestroyNotify(self.event.into()),
+            UnmapNotify => EventType::UnmapNotify(self.event.into()),
+            MapNotify => EventType::MapNotify(self.event.into()),
+            MapRequest => EventType::MapRequest(self.event.into()),
+            ReparentNotify => EventType::ReparentNotify(self.event.into()),
+            ConfigureNotify => EventType::ConfigureNotify(self.event.into()),
+            ConfigureRequest => EventType::ConfigureRequest(self.event.into()),
+            GravityNotify => EventType::GravityNotify(self.event.into()),
+            ResizeRequest => EventType::ResizeRequest(self.event.into()),
+            CirculateNotify => EventType::CirculateNotify(self.event.into()),
+            CirculateRequest => EventType::CirculateRequest(self.event.into()),
+            PropertyNotify => EventType::PropertyNotify(self.event.into()),
+            SelectionClear => EventType::SelectionClear(self.event.into()),
+            SelectionRequest => EventType::SelectionRequest(self.event.into()),
+            SelectionNotify => EventType::SelectionNotify(self.event.into()),
+            ColormapNotify => EventType::ColormapNotify(self.event.into()),
+            ClientMessage => EventType::ClientMessage(self.event.into()),
+            MappingNotify => EventType::MappingNotify(self.event.into()),
+
             _ => EventType::Unimplemented,
         };
 
@@ -92,24 +181,91 @@ impl Display {
         let mut event = Event::new();
         unsafe { XNextEvent(self.display, &mut event.event as *mut XEvent) };
 
+        // Before the conversion, which could already look up keysyms
+        update_mappings(&mut event.event);
+
         event.get_data()
     }
-}
 
-fn modifier_to_xlib_mod(modifier: Modifier) -> c_uint {
-    match modifier {
-        Modifier::Shift => ShiftMask,
-        Modifier::Lock => LockMask,
-        Modifier::Control => ControlMask,
-        Modifier::Alt => Mod1Mask,
-        Modifier::Num => Mod2Mask,
-        Modifier::Super => Mod4Mask,
-        Modifier::ScrollLock => Mod5Mask,
+    /// Like `get_event`, but returns `None` instead of waiting if there are no events
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// // Handle everything that arrived, then go do something else
+    /// while let Some(event) = display.poll_event() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn poll_event(&self) -> Option<EventData> {
+        if self.pending() == 0 {
+            return None;
+        }
+
+        Some(self.get_event())
+    }
+
+    /// How many events are waiting to be read with `get_event` or `poll_event`, this also
+    /// sends out any request that is still buffered
+    pub fn pending(&self) -> usize {
+        unsafe { XPending(self.display) as usize }
+    }
+
+    /// Like `get_event`, but gives up after `timeout`
+    /// # Examples
+    /// A clock that updates every second, even if nothing happens
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// loop {
+    ///     match display.wait_event_timeout(Duration::from_secs(1)) {
+    ///         Some(event) => { /* handle the event */ }
+    ///         None => { /* redraw the clock */ }
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_event_timeout(&self, timeout: Duration) -> Option<EventData> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            // This also reads whatever arrived on the connection into Xlib's queue
+            if let Some(event) = self.poll_event() {
+                return Some(event);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+
+            // Rounding up, otherwise we would spin for the last sub-millisecond
+            let timeout_ms = remaining.as_micros().div_ceil(1000).min(i32::MAX as u128) as i32;
+            let mut poll_fd = libc::pollfd {
+                fd: unsafe { XConnectionNumber(self.display) },
+                events: libc::POLLIN,
+                revents: 0,
+            };
+
+            if unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) } < 0
+                && io::Error::last_os_error().kind() != io::ErrorKind::Interrupted
+            {
+                return None;
+            }
+        }
     }
 }
 
 impl Window {
-    /// Filters X11 key events to a specific key & modifier
+    /// Filters X11 key events to a specific key & modifiers. The grab keeps working while
+    /// Caps Lock, NumLock or ScrollLock are on, unless they are part of `modifiers`. If
+    /// the keyboard has no NumLock or ScrollLock key, asking for them is the same as not
+    /// asking, so the grab also fires without them. Nothing is grabbed if there is no key
+    /// called `key`, use `try_grab_key()` to get an error instead
     /// # Examples
     /// ```
     /// use undici::x11::{display::Display, window::Modifier};
@@ -118,26 +274,45 @@ impl Window {
     /// let root_window = display.get_root_window();
     ///
     /// root_window.grab_key("a", Modifier::Alt);
+    /// root_window.grab_key("t", Modifier::Control | Modifier::Alt);
     /// ```
-    pub fn grab_key(&self, key: &str, modifier: Modifier) {
-        // what c does to a mf
-        let key_c = CString::new(key).unwrap();
-        let key_c_p: *const c_char = key_c.as_ptr();
-
-        unsafe {
-            XGrabKey(
-                self.display,
-                XKeysymToKeycode(self.display, XStringToKeysym(key_c_p)) as i32,
-                modifier_to_xlib_mod(modifier),
-                self.id,
-                true.into(),
-                1,
-                1,
-            )
-        };
+    pub fn grab_key(&self, key: &str, modifiers: impl Into<Modifiers>) {
+        if let Ok(keycode) = self.keycode_of(key) {
+            self.grab_keycode(keycode, modifiers.into());
+        }
+    }
+
+    /// The keycode of the key called `key`. Keycode `0` is `AnyKey`, so an unknown name
+    /// must never get that far, or it would grab every key
+    fn keycode_of(&self, key: &str) -> Result<u8, KeyError> {
+        let keysym = Keysym::from_name(key).ok_or_else(|| KeyError::UnknownKey(key.to_string()))?;
+
+        match unsafe { XKeysymToKeycode(self.display, keysym.0) } {
+            0 => Err(KeyError::NotOnKeyboard(key.to_string())),
+            keycode => Ok(keycode),
+        }
     }
 
-    /// Filters X11 mouse buttons events to a specific mouse button & modifier
+    fn grab_keycode(&self, keycode: u8, modifiers: Modifiers) {
+        let map = ModifierMap::get(self.display);
+
+        for mask in map.lock_variants(map.mask_of(modifiers)) {
+            unsafe {
+                XGrabKey(
+                    self.display,
+                    keycode as i32,
+                    mask,
+                    self.id,
+                    true.into(),
+                    1,
+                    1,
+                )
+            };
+        }
+    }
+
+    /// Filters X11 mouse buttons events to a specific mouse button & modifiers, like with
+    /// `grab_key()` the lock keys are ignored
     /// # Examples
     /// This only makes X11 look for mouse events with the left mouse key, while pressing
     /// alt
@@ -149,25 +324,182 @@ impl Window {
     ///
     /// root_window.grab_mouse_button(MouseButton::Left, Modifier::Alt);
     /// ```
-    pub fn grab_mouse_button(&self, mouse_button: MouseButton, modifier: Modifier) {
-        unsafe {
-            XGrabButton(
-                self.display,
-                mouse_button.as_c_uint(),
-                modifier_to_xlib_mod(modifier),
-                self.id,
-                true.into(),
-                (ButtonPressMask | ButtonReleaseMask | PointerMotionMask) as u32,
-                1,
-                1,
-                0,
-                0,
+    pub fn grab_mouse_button(&self, mouse_button: MouseButton, modifiers: impl Into<Modifiers>) {
+        let map = ModifierMap::get(self.display);
+
+        for mask in map.lock_variants(map.mask_of(modifiers.into())) {
+            unsafe {
+                XGrabButton(
+                    self.display,
+                    mouse_button.as_c_uint(),
+                    mask,
+                    self.id,
+                    true.into(),
+                    (ButtonPressMask | ButtonReleaseMask | PointerMotionMask) as u32,
+                    1,
+                    1,
+                    0,
+                    0,
+                )
+            };
+        }
+    }
+
+    /// Undoes a `grab_key()` with the same key and modifiers, lock variants included.
+    /// Nothing happens if there is no key called `key`
+    /// # Examples
+    /// ```
+    /// use undici::x11::{display::Display, window::Modifier};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// root_window.grab_key("a", Modifier::Alt);
+    /// root_window.ungrab_key("a", Modifier::Alt);
+    /// ```
+    pub fn ungrab_key(&self, key: &str, modifiers: impl Into<Modifiers>) {
+        if let Ok(keycode) = self.keycode_of(key) {
+            self.ungrab_keycode(keycode, modifiers.into());
+        }
+    }
+
+    /// Like `ungrab_key`, but reports errors, for example `KeyError::UnknownKey` for a
+    /// misspelled key name
+    pub fn try_ungrab_key(
+        &self,
+        key: &str,
+        modifiers: impl Into<Modifiers>,
+    ) -> Result<(), KeyError> {
+        let keycode = self.keycode_of(key)?;
+        checked(self.display, || {
+            self.ungrab_keycode(keycode, modifiers.into())
+        })?;
+
+        Ok(())
+    }
+
+    fn ungrab_keycode(&self, keycode: u8, modifiers: Modifiers) {
+        let map = ModifierMap::get(self.display);
+
+        for mask in map.lock_variants(map.mask_of(modifiers)) {
+            unsafe { XUngrabKey(self.display, keycode as i32, mask, self.id) };
+        }
+    }
+
+    /// Undoes a `grab_mouse_button()` with the same button and modifiers
+    pub fn ungrab_mouse_button(&self, mouse_button: MouseButton, modifiers: impl Into<Modifiers>) {
+        let map = ModifierMap::get(self.display);
+
+        for mask in map.lock_variants(map.mask_of(modifiers.into())) {
+            unsafe { XUngrabButton(self.display, mouse_button.as_c_uint(), mask, self.id) };
+        }
+    }
+
+    /// Choose which events this window will report, this replaces the previous selection
+    /// # Examples
+    /// ```
+    /// use undici::x11::{common::EventMask, display::Display};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// root_window.select_input(EventMask::PROPERTY_CHANGE | EventMask::FOCUS_CHANGE);
+    /// ```
+    pub fn select_input(&self, event_mask: EventMask) {
+        unsafe { XSelectInput(self.display, self.id, event_mask.0) };
+    }
+
+    /// Become the window manager: from now on, when a child of this window (usually the
+    /// root window) wants to be mapped, moved, resized or restacked, nothing happens and
+    /// we get a `MapRequest`, `ConfigureRequest` or `CirculateRequest` event instead, which
+    /// we can grant, modify or deny. This also reports the same events as
+    /// `grab_children_substucture()`
+    ///
+    /// Only one client at a time can do this, if another window manager is already
+    /// running the error will have the `ErrorCode::BadAccess` code. The events this
+    /// window already reports keep being reported
+    /// # Examples
+    /// ```no_run
+    /// use undici::x11::{display::Display, events::event::EventType};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// root_window
+    ///     .manage_substructure()
+    ///     .expect("another window manager is already running");
+    ///
+    /// loop {
+    ///     match display.get_event().type_ {
+    ///         EventType::MapRequest(request) => {
+    ///             request.grant().ok();
+    ///         }
+    ///
+    ///         EventType::ConfigureRequest(request) => {
+    ///             // Nobody gets to be wider than 800 pixels
+    ///             let mut changes = request.changes;
+    ///             changes.width = changes.width.map(|width| width.min(800));
+    ///
+    ///             request.grant_with(&changes).ok();
+    ///         }
+    ///
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn manage_substructure(&self) -> Result<(), ProtocolError> {
+        // `select_input()` replaces the whole selection, so we keep what was already there
+        let selected = EventMask(self.try_get_attributes()?.your_event_mask);
+
+        checked(self.display, || {
+            self.select_input(
+                selected | EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
             )
-        };
+        })
     }
 
     /// Listen for window creation events
     pub fn grab_children_substucture(&self) {
         unsafe { XSelectInput(self.display, self.id, SubstructureNotifyMask) };
     }
+
+    /// Like `grab_key`, but reports errors, for example a `BadAccess` if another client
+    /// already grabbed the same combination, or `KeyError::UnknownKey` for a misspelled
+    /// key name
+    /// # Examples
+    /// ```
+    /// use undici::x11::{display::Display, errors::KeyError, window::Modifier};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// assert_eq!(
+    ///     root_window.try_grab_key("Retrun", Modifier::Alt),
+    ///     Err(KeyError::UnknownKey("Retrun".to_string()))
+    /// );
+    /// ```
+    pub fn try_grab_key(&self, key: &str, modifiers: impl Into<Modifiers>) -> Result<(), KeyError> {
+        let keycode = self.keycode_of(key)?;
+        checked(self.display, || {
+            self.grab_keycode(keycode, modifiers.into())
+        })?;
+
+        Ok(())
+    }
+
+    /// Like `grab_mouse_button`, but reports errors, for example a `BadAccess` if another
+    /// client already grabbed the same combination
+    pub fn try_grab_mouse_button(
+        &self,
+        mouse_button: MouseButton,
+        modifiers: impl Into<Modifiers>,
+    ) -> Result<(), ProtocolError> {
+        checked(self.display, || {
+            self.grab_mouse_button(mouse_button, modifiers)
+        })
+    }
+
+    pub fn try_grab_children_substucture(&self) -> Result<(), ProtocolError> {
+        checked(self.display, || self.grab_children_substucture())
+    }
 }
@@ -0,0 +1,88 @@
+use crate::x11::{common::Vector2, window::Window};
+use x11::xlib::{XEvent, XExposeEvent, XGraphicsExposeEvent, XNoExposeEvent};
+
+/// Part of a window needs to be redrawn
+#[derive(PartialEq)]
+pub struct ExposeData {
+    pub window: Window,
+
+    /// The area that needs to be redrawn, relative to the window
+    pub position: Vector2<i32>,
+    pub scale: Vector2<i32>,
+
+    /// How many `Expose` events for the same window are still coming, if you redraw the
+    /// whole window anyway you can ignore the ones where this is not `0`
+    pub count: i32,
+}
+
+/// Part of the destination of a `copy_area()` could not be copied because that part of
+/// the source was covered or outside of it, so it has to be redrawn. Only sent when the
+/// graphics context has graphics exposures on, which is the default
+#[derive(PartialEq)]
+pub struct GraphicsExposeData {
+    /// The id of the window or pixmap that was copied to
+    pub drawable: u64,
+
+    /// The area that needs to be redrawn, relative to the drawable
+    pub position: Vector2<i32>,
+    pub scale: Vector2<i32>,
+
+    /// How many `GraphicsExpose` events for the same copy are still coming
+    pub count: i32,
+
+    /// The request that caused it, `62` for `CopyArea` and `63` for `CopyPlane`
+    pub major_code: i32,
+    pub minor_code: i32,
+}
+
+/// A `copy_area()` could copy everything, so no `GraphicsExpose` event is coming for it
+#[derive(PartialEq)]
+pub struct NoExposeData {
+    /// The id of the window or pixmap that was copied to
+    pub drawable: u64,
+    pub major_code: i32,
+    pub minor_code: i32,
+}
+
+impl From<XEvent> for ExposeData {
+    fn from(xevent: XEvent) -> Self {
+        let xexpose: XExposeEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xexpose.window,
+                display: xexpose.display,
+            },
+            position: Vector2::new(xexpose.x, xexpose.y),
+            scale: Vector2::new(xexpose.width, xexpose.height),
+            count: xexpose.count,
+        }
+    }
+}
+
+impl From<XEvent> for GraphicsExposeData {
+    fn from(xevent: XEvent) -> Self {
+        let xexpose: XGraphicsExposeEvent = xevent.into();
+
+        Self {
+            drawable: xexpose.drawable,
+            position: Vector2::new(xexpose.x, xexpose.y),
+            scale: Vector2::new(xexpose.width, xexpose.height),
+            count: xexpose.count,
+            major_code: xexpose.major_code,
+            minor_code: xexpose.minor_code,
+        }
+    }
+}
+
+impl From<XEvent> for NoExposeData {
+    fn from(xevent: XEvent) -> Self {
+        let xno_expose: XNoExposeEvent = xevent.into();
+
+        Self {
+            drawable: xno_expose.drawable,
+            major_code: xno_expose.major_code,
+            minor_code: xno_expose.minor_code,
+        }
+    }
+}
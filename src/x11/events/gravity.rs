@@ -0,0 +1,33 @@
+use crate::x11::{common::Vector2, window::Window};
+use x11::xlib::{XEvent, XGravityEvent};
+
+/// A window was moved because its parent was resized and its gravity says it should
+/// follow one of the parent's sides
+#[derive(PartialEq)]
+pub struct GravityData {
+    pub window: Window,
+
+    /// The window that got the event, either `window` or its parent
+    pub event_window: Window,
+
+    /// Relative to the parent
+    pub position: Vector2<i32>,
+}
+
+impl From<XEvent> for GravityData {
+    fn from(xevent: XEvent) -> Self {
+        let xgravity: XGravityEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xgravity.window,
+                display: xgravity.display,
+            },
+            event_window: Window {
+                id: xgravity.event,
+                display: xgravity.display,
+            },
+            position: Vector2::new(xgravity.x, xgravity.y),
+        }
+    }
+}
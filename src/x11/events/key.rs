@@ -1,28 +1,82 @@
-use std::ffi::{c_char, CStr};
-use x11::xlib::{XEvent, XKeyEvent, XKeycodeToKeysym, XKeysymToString};
+use crate::x11::{
+    common::Vector2,
+    keyboard::{Keysym, ModifierMap},
+    window::Modifiers,
+};
+use std::ffi::c_char;
+use x11::xlib::{XEvent, XKeyEvent, XKeycodeToKeysym, XLookupString};
+
+/// Big enough for anything a single key press can type
+const TEXT_BUFFER_SIZE: usize = 32;
 
 #[derive(PartialEq)]
 pub struct KeyEventData {
+    /// The name of the key as if no modifier was pressed, so both `a` and `Shift+a` give
+    /// `"a"`. Empty if the key has no name
     pub key: String,
+
+    /// The symbol of the key with the modifiers applied, `Shift+a` gives `A`
+    pub keysym: Keysym,
+
+    /// The physical key, this does not depend on the layout or the modifiers
+    pub keycode: u8,
+
+    /// The modifiers that were held when the key was pressed, this includes locks like
+    /// `Modifiers::NUM` and `Modifiers::LOCK` (Caps Lock)
+    pub modifiers: Modifiers,
+
+    /// In milliseconds, from when the X server started
+    pub time: u64,
+
+    /// Relative to the window that got the event
+    pub position: Vector2<i32>,
+    pub root_position: Vector2<i32>,
+
+    /// What the key would type, for example `"A"` for `Shift+a`. Empty for keys that don't
+    /// type anything like `Shift` or `F1`, and control characters for keys like `Return`
+    pub text: String,
 }
 
 impl From<XEvent> for KeyEventData {
     fn from(xevent: XEvent) -> Self {
-        let xkey: XKeyEvent = xevent.into();
+        let mut xkey: XKeyEvent = xevent.into();
 
-        let key_sym = unsafe { XKeycodeToKeysym(xkey.display, xkey.keycode as u8, 0) };
+        let unmodified_keysym =
+            Keysym(unsafe { XKeycodeToKeysym(xkey.display, xkey.keycode as u8, 0) });
+
+        let mut keysym = 0;
+        let mut buffer = [0 as c_char; TEXT_BUFFER_SIZE];
+        let length = unsafe {
+            XLookupString(
+                &mut xkey,
+                buffer.as_mut_ptr(),
+                buffer.len() as i32,
+                &mut keysym,
+                std::ptr::null_mut(),
+            )
+        };
 
-        // Converting C's char* to rust's String
-        let key = unsafe {
-            let key_c: *mut c_char = XKeysymToString(key_sym);
-            let key_cstring = CStr::from_ptr(key_c);
+        let keysym = Keysym(keysym);
 
-            key_cstring
-                .to_str()
-                .expect("could not convert C keysm to a Rust string")
-                .to_string()
+        // XLookupString gives back Latin-1, which maps 1:1 to the first 256 code points,
+        // for everything else we can still use the keysym if it's a Unicode one
+        let text = match length {
+            0 => keysym.unicode_char().map(String::from).unwrap_or_default(),
+            _ => buffer[..length as usize]
+                .iter()
+                .map(|byte| *byte as u8 as char)
+                .collect(),
         };
 
-        Self { key }
+        Self {
+            key: unmodified_keysym.name().unwrap_or_default(),
+            keysym,
+            keycode: xkey.keycode as u8,
+            modifiers: ModifierMap::get(xkey.display).modifiers_of(xkey.state),
+            time: xkey.time,
+            position: Vector2::new(xkey.x, xkey.y),
+            root_position: Vector2::new(xkey.x_root, xkey.y_root),
+            text,
+        }
     }
 }
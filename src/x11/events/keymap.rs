@@ -0,0 +1,36 @@
+use x11::xlib::{XEvent, XKeymapEvent};
+
+/// The keys that were held when the pointer entered a window or the window got the focus,
+/// it arrives right after the `EnterNotify` or `FocusIn` event
+#[derive(PartialEq)]
+pub struct KeymapData {
+    /// One bit for every keycode, `keys[0] & 1` is keycode `0`
+    pub keys: [u8; 32],
+}
+
+impl KeymapData {
+    /// # Examples
+    /// ```
+    /// use undici::x11::events::keymap::KeymapData;
+    ///
+    /// let mut keys = [0; 32];
+    /// keys[4] = 0b10;
+    ///
+    /// let keymap = KeymapData { keys };
+    /// assert!(keymap.is_pressed(33));
+    /// assert!(!keymap.is_pressed(32));
+    /// ```
+    pub fn is_pressed(&self, keycode: u8) -> bool {
+        self.keys[keycode as usize / 8] & (1 << (keycode % 8)) != 0
+    }
+}
+
+impl From<XEvent> for KeymapData {
+    fn from(xevent: XEvent) -> Self {
+        let xkeymap: XKeymapEvent = xevent.into();
+
+        Self {
+            keys: xkeymap.key_vector.map(|byte| byte as u8),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::x11::{errors::ProtocolError, window::Window};
+use x11::xlib::{XEvent, XMapEvent, XMapRequestEvent, XUnmapEvent};
+
+/// A window asked to be shown, this only arrives to the window that owns the substructure
+/// redirection of its parent (the window manager)
+#[derive(PartialEq)]
+pub struct MapRequestData {
+    pub window: Window,
+    pub parent_window: Window,
+}
+
+#[derive(PartialEq)]
+pub struct MapData {
+    pub window: Window,
+
+    /// The window that got the event, either `window` or its parent
+    pub event_window: Window,
+    pub override_redirect: bool,
+}
+
+#[derive(PartialEq)]
+pub struct UnmapData {
+    pub window: Window,
+
+    /// The window that got the event, either `window` or its parent
+    pub event_window: Window,
+
+    /// If the window was unmapped because its parent got resized with a `win_gravity` of
+    /// `UnmapGravity`
+    pub from_configure: bool,
+}
+
+impl MapRequestData {
+    /// Maps the window, this is the place to move, resize or reparent it before it shows
+    /// up. Fails if the window was destroyed in the meantime
+    pub fn grant(&self) -> Result<(), ProtocolError> {
+        self.window.try_map()
+    }
+
+    /// Keeps the window hidden, this does not need to tell X anything, the window just
+    /// stays unmapped
+    pub fn deny(&self) {}
+}
+
+impl From<XEvent> for MapRequestData {
+    fn from(xevent: XEvent) -> Self {
+        let xmaprequest: XMapRequestEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xmaprequest.window,
+                display: xmaprequest.display,
+            },
+            parent_window: Window {
+                id: xmaprequest.parent,
+                display: xmaprequest.display,
+            },
+        }
+    }
+}
+
+impl From<XEvent> for MapData {
+    fn from(xevent: XEvent) -> Self {
+        let xmap: XMapEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xmap.window,
+                display: xmap.display,
+            },
+            event_window: Window {
+                id: xmap.event,
+                display: xmap.display,
+            },
+            override_redirect: xmap.override_redirect != 0,
+        }
+    }
+}
+
+impl From<XEvent> for UnmapData {
+    fn from(xevent: XEvent) -> Self {
+        let xunmap: XUnmapEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xunmap.window,
+                display: xunmap.display,
+            },
+            event_window: Window {
+                id: xunmap.event,
+                display: xunmap.display,
+            },
+            from_configure: xunmap.from_configure != 0,
+        }
+    }
+}
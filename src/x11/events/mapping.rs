@@ -0,0 +1,62 @@
+use crate::x11::keyboard::ModifierMap;
+use x11::xlib::{
+    MappingKeyboard, MappingModifier, MappingNotify, XEvent, XMappingEvent, XRefreshKeyboardMapping,
+};
+
+/// What changed in a `MappingNotify` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingRequest {
+    Modifier,
+    Keyboard,
+    Pointer,
+}
+
+/// The keyboard layout, the modifier keys or the mouse buttons were remapped
+#[derive(PartialEq)]
+pub struct MappingData {
+    pub request: MappingRequest,
+
+    /// The range of keycodes that changed, only meaningful for `MappingRequest::Keyboard`
+    pub first_keycode: u8,
+    pub count: i32,
+}
+
+impl From<XEvent> for MappingData {
+    #[allow(non_upper_case_globals)]
+    fn from(xevent: XEvent) -> Self {
+        let xmapping: XMappingEvent = xevent.into();
+
+        let request = match xmapping.request {
+            MappingModifier => MappingRequest::Modifier,
+            MappingKeyboard => MappingRequest::Keyboard,
+            _ => MappingRequest::Pointer,
+        };
+
+        Self {
+            request,
+            first_keycode: xmapping.first_keycode as u8,
+            count: xmapping.count,
+        }
+    }
+}
+
+/// Xlib and `ModifierMap` cache the keyboard and modifier mappings, when `xevent` is a
+/// `MappingNotify` this throws the old ones away, otherwise keysyms and modifiers would
+/// keep being looked up with the old layout. `Display::get_event()` calls this for every
+/// event it reads
+#[allow(non_upper_case_globals)]
+pub(crate) fn update_mappings(xevent: &mut XEvent) {
+    if unsafe { xevent.type_ } != MappingNotify {
+        return;
+    }
+
+    let xmapping = unsafe { &mut xevent.mapping };
+
+    if matches!(xmapping.request, MappingKeyboard | MappingModifier) {
+        unsafe { XRefreshKeyboardMapping(xmapping) };
+    }
+
+    if xmapping.request == MappingModifier {
+        ModifierMap::forget(xmapping.display);
+    }
+}
@@ -1,5 +1,24 @@
 pub mod button;
+pub mod circulate;
+pub mod client_message;
+pub mod colormap;
+pub mod configure;
 pub mod create;
+pub mod crossing;
+pub mod destroy;
 pub mod event;
+pub mod expose;
+pub mod gravity;
 pub mod key;
+pub mod keymap;
+pub mod map;
+pub mod mapping;
 pub mod motion;
+pub mod property;
+pub mod reparent;
+pub mod resize;
+pub mod selection;
+pub mod visibility;
+
+#[cfg(feature = "async")]
+pub mod stream;
@@ -0,0 +1,29 @@
+use crate::x11::{atom::Atom, window::Window};
+use x11::xlib::{PropertyDelete, XEvent, XPropertyEvent};
+
+/// A property of a window was changed or deleted
+#[derive(PartialEq)]
+pub struct PropertyData {
+    pub window: Window,
+    pub atom: Atom,
+    pub time: u64,
+
+    /// `true` if the property was deleted, `false` if it was changed
+    pub deleted: bool,
+}
+
+impl From<XEvent> for PropertyData {
+    fn from(xevent: XEvent) -> Self {
+        let xproperty: XPropertyEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xproperty.window,
+                display: xproperty.display,
+            },
+            atom: Atom(xproperty.atom),
+            time: xproperty.time,
+            deleted: xproperty.state == PropertyDelete,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::x11::{common::Vector2, window::Window};
+use x11::xlib::{XEvent, XReparentEvent};
+
+#[derive(PartialEq)]
+pub struct ReparentData {
+    pub window: Window,
+
+    /// The window that got the event, `window`, its old parent or its new parent
+    pub event_window: Window,
+    pub parent_window: Window,
+
+    /// Relative to the new parent
+    pub position: Vector2<i32>,
+    pub override_redirect: bool,
+}
+
+impl From<XEvent> for ReparentData {
+    fn from(xevent: XEvent) -> Self {
+        let xreparent: XReparentEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xreparent.window,
+                display: xreparent.display,
+            },
+            event_window: Window {
+                id: xreparent.event,
+                display: xreparent.display,
+            },
+            parent_window: Window {
+                id: xreparent.parent,
+                display: xreparent.display,
+            },
+            position: Vector2::new(xreparent.x, xreparent.y),
+            override_redirect: xreparent.override_redirect != 0,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::x11::{common::Vector2, window::Window};
+use x11::xlib::{XEvent, XResizeRequestEvent};
+
+/// Another client tried to resize a window, this only arrives to the client that selected
+/// `EventMask::RESIZE_REDIRECT` on it, and the window keeps its size until that client
+/// resizes it itself
+#[derive(PartialEq)]
+pub struct ResizeRequestData {
+    pub window: Window,
+
+    /// The size that was asked for, not counting the border
+    pub scale: Vector2<i32>,
+}
+
+impl From<XEvent> for ResizeRequestData {
+    fn from(xevent: XEvent) -> Self {
+        let xresize: XResizeRequestEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xresize.window,
+                display: xresize.display,
+            },
+            scale: Vector2::new(xresize.width, xresize.height),
+        }
+    }
+}
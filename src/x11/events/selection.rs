@@ -0,0 +1,91 @@
+use crate::x11::{atom::Atom, window::Window};
+use x11::xlib::{XEvent, XSelectionClearEvent, XSelectionEvent, XSelectionRequestEvent};
+
+/// Another client wants the content of a selection we own (the clipboard for example)
+#[derive(PartialEq)]
+pub struct SelectionRequestData {
+    pub owner: Window,
+    pub requestor: Window,
+    pub selection: Atom,
+
+    /// The format the requestor wants the data in
+    pub target: Atom,
+
+    /// The property of `requestor` where the data should be put, `None` for old clients
+    /// which expect us to use `target` as the property
+    pub property: Option<Atom>,
+    pub time: u64,
+}
+
+/// The answer to a selection conversion we requested
+#[derive(PartialEq)]
+pub struct SelectionNotifyData {
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+
+    /// Where the data was put, `None` if the owner could not convert the selection
+    pub property: Option<Atom>,
+    pub time: u64,
+}
+
+/// We lost the ownership of a selection
+#[derive(PartialEq)]
+pub struct SelectionClearData {
+    pub window: Window,
+    pub selection: Atom,
+    pub time: u64,
+}
+
+impl From<XEvent> for SelectionRequestData {
+    fn from(xevent: XEvent) -> Self {
+        let xselection: XSelectionRequestEvent = xevent.into();
+
+        Self {
+            owner: Window {
+                id: xselection.owner,
+                display: xselection.display,
+            },
+            requestor: Window {
+                id: xselection.requestor,
+                display: xselection.display,
+            },
+            selection: Atom(xselection.selection),
+            target: Atom(xselection.target),
+            property: (xselection.property != 0).then_some(Atom(xselection.property)),
+            time: xselection.time,
+        }
+    }
+}
+
+impl From<XEvent> for SelectionNotifyData {
+    fn from(xevent: XEvent) -> Self {
+        let xselection: XSelectionEvent = xevent.into();
+
+        Self {
+            requestor: Window {
+                id: xselection.requestor,
+                display: xselection.display,
+            },
+            selection: Atom(xselection.selection),
+            target: Atom(xselection.target),
+            property: (xselection.property != 0).then_some(Atom(xselection.property)),
+            time: xselection.time,
+        }
+    }
+}
+
+impl From<XEvent> for SelectionClearData {
+    fn from(xevent: XEvent) -> Self {
+        let xselection: XSelectionClearEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xselection.window,
+                display: xselection.display,
+            },
+            selection: Atom(xselection.selection),
+            time: xselection.time,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use super::event::EventData;
+use crate::x11::display::Display;
+use async_io::Async;
+use futures_core::Stream;
+use std::{
+    io,
+    os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The X connection's file descriptor, which is owned (and closed) by the `Display`, not
+/// by us
+struct ConnectionFd(RawFd);
+
+impl AsFd for ConnectionFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.0) }
+    }
+}
+
+/// Can be created with `Display`'s `.event_stream()` method
+pub struct EventStream<'a> {
+    display: &'a Display,
+    connection: Async<ConnectionFd>,
+}
+
+impl Display {
+    /// The same events as `get_event`, but as a `Stream`. Instead of blocking it waits for
+    /// the connection to the X server to become readable, so it works with any async
+    /// runtime (tokio included) without needing a thread per display
+    /// # Examples
+    /// ```
+    /// use futures_lite::{future::{self, FutureExt}, StreamExt};
+    /// use std::time::Duration;
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let mut events = display.event_stream().expect("could not watch the connection");
+    ///
+    /// future::block_on(async {
+    ///     let timeout = async {
+    ///         async_io::Timer::after(Duration::from_millis(100)).await;
+    ///         None
+    ///     };
+    ///
+    ///     // Nobody asked for any event, so nothing should arrive
+    ///     assert!(events.next().or(timeout).await.is_none());
+    /// });
+    /// ```
+    pub fn event_stream(&self) -> io::Result<EventStream<'_>> {
+        Ok(EventStream {
+            display: self,
+            connection: Async::new(ConnectionFd(self.as_raw_fd()))?,
+        })
+    }
+}
+
+impl Stream for EventStream<'_> {
+    type Item = EventData;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            // Xlib can read events into its queue while doing other requests, so we have
+            // to check the queue first, the fd would not become readable for those
+            if let Some(event) = self.display.poll_event() {
+                return Poll::Ready(Some(event));
+            }
+
+            match self.connection.poll_readable(cx) {
+                Poll::Ready(Ok(())) => continue,
+                Poll::Ready(Err(_)) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::x11::window::Window;
+use x11::xlib::{VisibilityPartiallyObscured, VisibilityUnobscured, XEvent, XVisibilityEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Unobscured,
+    PartiallyObscured,
+    FullyObscured,
+}
+
+/// How much of a window can be seen changed, useful to stop drawing when nobody can see it
+#[derive(PartialEq)]
+pub struct VisibilityData {
+    pub window: Window,
+    pub visibility: Visibility,
+}
+
+impl From<XEvent> for VisibilityData {
+    #[allow(non_upper_case_globals)]
+    fn from(xevent: XEvent) -> Self {
+        let xvisibility: XVisibilityEvent = xevent.into();
+
+        Self {
+            window: Window {
+                id: xvisibility.window,
+                display: xvisibility.display,
+            },
+            visibility: match xvisibility.state {
+                VisibilityUnobscured => Visibility::Unobscured,
+                VisibilityPartiallyObscured => Visibility::PartiallyObscured,
+                _ => Visibility::FullyObscured,
+            },
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use super::{display::Display, drawable::Drawable};
+use std::ptr::null_mut;
+use x11::xlib::{
+    self, XChangeGC, XCreateGC, XFreeGC, XGCValues, XSetBackground, XSetFillStyle, XSetForeground,
+    XSetFunction,
+};
+
+/// How lines are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    Solid,
+
+    /// Only the dashes are drawn, the gaps are left alone
+    OnOffDash,
+
+    /// The gaps are drawn with the background color
+    DoubleDash,
+}
+
+/// What filled shapes are filled with, the tile and the stipple default to a plain
+/// foreground
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillStyle {
+    Solid,
+    Tiled,
+    Stippled,
+    OpaqueStippled,
+}
+
+/// How the pixels that get drawn are combined with the ones that are already there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawFunction {
+    /// Just replaces the old pixels, the default
+    Copy,
+
+    /// Drawing the same thing twice puts back what was there before, which makes it
+    /// great for rubber bands, that have to be erased without knowing what's under them
+    Xor,
+
+    /// Ignores the color and inverts the old pixels
+    Invert,
+    And,
+    Or,
+
+    /// Sets the pixels to 0
+    Clear,
+
+    /// Sets every bit of the pixels to 1
+    Set,
+
+    /// Draws nothing
+    NoOp,
+}
+
+impl From<LineStyle> for i32 {
+    fn from(line_style: LineStyle) -> Self {
+        match line_style {
+            LineStyle::Solid => xlib::LineSolid,
+            LineStyle::OnOffDash => xlib::LineOnOffDash,
+            LineStyle::DoubleDash => xlib::LineDoubleDash,
+        }
+    }
+}
+
+impl From<FillStyle> for i32 {
+    fn from(fill_style: FillStyle) -> Self {
+        match fill_style {
+            FillStyle::Solid => xlib::FillSolid,
+            FillStyle::Tiled => xlib::FillTiled,
+            FillStyle::Stippled => xlib::FillStippled,
+            FillStyle::OpaqueStippled => xlib::FillOpaqueStippled,
+        }
+    }
+}
+
+impl From<DrawFunction> for i32 {
+    fn from(function: DrawFunction) -> Self {
+        match function {
+            DrawFunction::Copy => xlib::GXcopy,
+            DrawFunction::Xor => xlib::GXxor,
+            DrawFunction::Invert => xlib::GXinvert,
+            DrawFunction::And => xlib::GXand,
+            DrawFunction::Or => xlib::GXor,
+            DrawFunction::Clear => xlib::GXclear,
+            DrawFunction::Set => xlib::GXset,
+            DrawFunction::NoOp => xlib::GXnoop,
+        }
+    }
+}
+
+/// A graphics context, it holds the settings drawing calls use, like the color and the
+/// line width. It's freed when dropped, so it can't outlive its `Display`. Can be created
+/// with `Display`'s `.create_gc()` method, and it can be used to draw on any window or
+/// pixmap with the same depth and screen
+pub struct GC<'a> {
+    pub(crate) gc: xlib::GC,
+    display: &'a Display,
+}
+
+impl Display {
+    /// Creates a graphics context for drawing on `drawable` and everything with the same
+    /// depth and screen, with the default settings, which draw in black with 1 pixel wide
+    /// solid lines
+    /// # Examples
+    /// ```
+    /// use undici::x11::{
+    ///     common::{Rect, Vector2},
+    ///     display::Display,
+    ///     drawable::Drawable,
+    ///     gc::LineStyle,
+    /// };
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screen = display.default_screen();
+    ///
+    /// let window = display
+    ///     .create_window()
+    ///     .scale(Vector2::new(200, 100))
+    ///     .background(screen.black_pixel)
+    ///     .build()
+    ///     .expect("could not create window");
+    ///
+    /// window.map();
+    ///
+    /// let gc = display.create_gc(&window);
+    /// gc.set_foreground(screen.white_pixel);
+    /// gc.set_line_width(2);
+    /// gc.set_line_style(LineStyle::OnOffDash);
+    ///
+    /// window.draw_rectangle(&gc, Rect::new(10, 10, 180, 80));
+    /// window.draw_line(&gc, Vector2::new(10, 10), Vector2::new(190, 90));
+    /// window.fill_rectangle(&gc, Rect::new(90, 40, 20, 20));
+    /// ```
+    pub fn create_gc(&self, drawable: &impl Drawable) -> GC<'_> {
+        GC {
+            gc: unsafe { XCreateGC(self.display, drawable.drawable_id(), 0, null_mut()) },
+            display: self,
+        }
+    }
+}
+
+impl GC<'_> {
+    /// The color of what gets drawn, a pixel value like `Screen.white_pixel` or one from
+    /// `Display::alloc_color()`
+    pub fn set_foreground(&self, pixel: u64) {
+        unsafe { XSetForeground(self.display.display, self.gc, pixel) };
+    }
+
+    /// The color of the gaps of `LineStyle::DoubleDash` lines, and of opaque stipples
+    pub fn set_background(&self, pixel: u64) {
+        unsafe { XSetBackground(self.display.display, self.gc, pixel) };
+    }
+
+    /// `0` draws 1 pixel wide lines with a faster algorithm, which can look slightly
+    /// different from `1`
+    pub fn set_line_width(&self, line_width: u32) {
+        let mut values: XGCValues = unsafe { std::mem::zeroed() };
+        values.line_width = line_width as i32;
+
+        unsafe {
+            XChangeGC(
+                self.display.display,
+                self.gc,
+                xlib::GCLineWidth as u64,
+                &mut values,
+            )
+        };
+    }
+
+    pub fn set_line_style(&self, line_style: LineStyle) {
+        let mut values: XGCValues = unsafe { std::mem::zeroed() };
+        values.line_style = line_style.into();
+
+        unsafe {
+            XChangeGC(
+                self.display.display,
+                self.gc,
+                xlib::GCLineStyle as u64,
+                &mut values,
+            )
+        };
+    }
+
+    pub fn set_fill_style(&self, fill_style: FillStyle) {
+        unsafe { XSetFillStyle(self.display.display, self.gc, fill_style.into()) };
+    }
+
+    pub fn set_function(&self, function: DrawFunction) {
+        unsafe { XSetFunction(self.display.display, self.gc, function.into()) };
+    }
+}
+
+impl Drop for GC<'_> {
+    fn drop(&mut self) {
+        unsafe { XFreeGC(self.display.display, self.gc) };
+    }
+}
@@ -0,0 +1,157 @@
+use super::{
+    common::EventMask,
+    cursor::Cursor,
+    display::Display,
+    errors::{checked_reply, GrabError},
+    window::Window,
+};
+use x11::xlib::{
+    AlreadyGrabbed, CurrentTime, GrabFrozen, GrabInvalidTime, GrabModeAsync, GrabNotViewable,
+    GrabSuccess, XFlush, XGrabKeyboard, XGrabPointer, XUngrabKeyboard, XUngrabPointer,
+};
+
+/// While this is alive every key event goes to the window that grabbed the keyboard,
+/// dropping it releases the grab, also when unwinding from a panic
+pub struct KeyboardGrab<'a> {
+    display: &'a Display,
+}
+
+/// While this is alive every pointer event goes to the window that grabbed the pointer,
+/// dropping it releases the grab, also when unwinding from a panic
+pub struct PointerGrab<'a> {
+    display: &'a Display,
+}
+
+#[allow(non_upper_case_globals)]
+fn grab_status_to_result(status: i32) -> Result<(), GrabError> {
+    match status {
+        GrabSuccess => Ok(()),
+        AlreadyGrabbed => Err(GrabError::AlreadyGrabbed),
+        GrabInvalidTime => Err(GrabError::InvalidTime),
+        GrabNotViewable => Err(GrabError::NotViewable),
+        GrabFrozen => Err(GrabError::Frozen),
+        status => Err(GrabError::Unknown(status)),
+    }
+}
+
+impl Display {
+    /// Sends every key event to `window` until the returned guard is dropped, even the
+    /// ones that other clients grabbed with `grab_key()`. `window` has to be mapped
+    /// # Examples
+    /// ```no_run
+    /// use undici::x11::{display::Display, events::event::EventType};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// let grab = display
+    ///     .grab_keyboard(root_window)
+    ///     .expect("someone else is grabbing the keyboard");
+    ///
+    /// // Every key goes to us until Escape is pressed
+    /// loop {
+    ///     if let EventType::KeyPress(key_event) = display.get_event().type_ {
+    ///         if key_event.key == "Escape" {
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// drop(grab);
+    /// ```
+    pub fn grab_keyboard(&self, window: Window) -> Result<KeyboardGrab<'_>, GrabError> {
+        let status = checked_reply(self.display, || unsafe {
+            XGrabKeyboard(
+                self.display,
+                window.id,
+                false.into(),
+                GrabModeAsync,
+                GrabModeAsync,
+                CurrentTime,
+            )
+        })?;
+
+        grab_status_to_result(status)?;
+
+        Ok(KeyboardGrab { display: self })
+    }
+
+    /// Sends the pointer events in `event_mask` to `window` until the returned guard is
+    /// dropped. If `confine_to` is `Some` the pointer can't leave that window, and if
+    /// `cursor` is `Some` it's shown for the whole grab, wherever the pointer is
+    /// # Examples
+    /// A region selector, like the ones of screenshot tools
+    /// ```no_run
+    /// use undici::x11::{
+    ///     common::EventMask,
+    ///     cursor::CursorShape,
+    ///     display::Display,
+    ///     events::event::EventType,
+    /// };
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    /// let crosshair = display.create_font_cursor(CursorShape::Crosshair);
+    ///
+    /// let grab = display
+    ///     .grab_pointer(
+    ///         root_window,
+    ///         EventMask::BUTTON_PRESS | EventMask::BUTTON_RELEASE,
+    ///         Some(root_window),
+    ///         Some(&crosshair),
+    ///     )
+    ///     .expect("someone else is grabbing the pointer");
+    ///
+    /// loop {
+    ///     if let EventType::MouseButtonRelease(_) = display.get_event().type_ {
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// drop(grab);
+    /// ```
+    pub fn grab_pointer(
+        &self,
+        window: Window,
+        event_mask: EventMask,
+        confine_to: Option<Window>,
+        cursor: Option<&Cursor>,
+    ) -> Result<PointerGrab<'_>, GrabError> {
+        let status = checked_reply(self.display, || unsafe {
+            XGrabPointer(
+                self.display,
+                window.id,
+                false.into(),
+                event_mask.0 as u32,
+                GrabModeAsync,
+                GrabModeAsync,
+                confine_to.map_or(0, |window| window.id),
+                cursor.map_or(0, |cursor| cursor.id),
+                CurrentTime,
+            )
+        })?;
+
+        grab_status_to_result(status)?;
+
+        Ok(PointerGrab { display: self })
+    }
+}
+
+impl Drop for KeyboardGrab<'_> {
+    fn drop(&mut self) {
+        // Without the flush the grab would stay until the next request that flushes
+        unsafe {
+            XUngrabKeyboard(self.display.display, CurrentTime);
+            XFlush(self.display.display);
+        }
+    }
+}
+
+impl Drop for PointerGrab<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            XUngrabPointer(self.display.display, CurrentTime);
+            XFlush(self.display.display);
+        }
+    }
+}
@@ -1,12 +1,144 @@
-use super::{common::Vector2, window::Window};
+use super::{
+    common::{Rect, Vector2},
+    drawable::Drawable,
+    errors::{checked, ImageError, ProtocolError},
+    gc::GC,
+    window::Window,
+};
 use crate::color::RGB;
-use x11::xlib::{XAllPlanes, XDestroyImage, XGetImage, XGetPixel, XImage, ZPixmap};
+use std::{mem::size_of, ptr::null_mut};
+use x11::xlib::{
+    _XDisplay, LSBFirst, MSBFirst, TrueColor, XCreateImage, XDefaultScreen, XDestroyImage,
+    XGetPixel, XImage, XInitImage, XMatchVisualInfo, XPutImage, XPutPixel, XVisualInfo,
+    XWindowAttributes, ZPixmap,
+};
+
+/// The order of the bytes of a pixel in memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+/// How the pixels of an `Image` are laid out, it comes from the visual of the window the
+/// image was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelFormat {
+    /// Which bits of a pixel value are red, green and blue, for example `0xFF0000`,
+    /// `0xFF00` and `0xFF` on the usual 24 bit visuals, but `0xF800`, `0x7E0` and `0x1F`
+    /// on 16 bit ones
+    pub red_mask: u64,
+    pub green_mask: u64,
+    pub blue_mask: u64,
+
+    /// How many bits of a pixel are actually used
+    pub depth: u32,
+
+    /// How many bits a pixel takes in memory, usually 32 for both 24 and 30 bit depths
+    pub bits_per_pixel: u32,
+    pub byte_order: ByteOrder,
+}
 
 /// Can be created from a `Window`'s `.get_image()` method
 pub struct Image {
     pub(crate) image: *mut XImage,
     pub width: u32,
     pub height: u32,
+    pub format: PixelFormat,
+}
+
+/// Takes the bits of `mask` out of `pixel` and scales them to 0-255, so that a 5 bit
+/// channel at its maximum is still 255
+fn decode_channel(pixel: u64, mask: u64) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+
+    let max = mask >> mask.trailing_zeros();
+    let value = (pixel & mask) >> mask.trailing_zeros();
+
+    ((value * 255 + max / 2) / max) as u8
+}
+
+/// The opposite of `decode_channel()`, scales `value` to the bits of `mask`
+fn encode_channel(value: u8, mask: u64) -> u64 {
+    if mask == 0 {
+        return 0;
+    }
+
+    let max = mask >> mask.trailing_zeros();
+
+    ((value as u64 * max + 127) / 255) << mask.trailing_zeros()
+}
+
+impl PixelFormat {
+    /// The format `Image::from_rgb()` uses: `0xRRGGBB` in 32 bits, with this machine's
+    /// byte order, which is also what most 24 bit visuals use
+    pub const RGB24: PixelFormat = PixelFormat {
+        red_mask: 0xFF_0000,
+        green_mask: 0x00_FF00,
+        blue_mask: 0x00_00FF,
+        depth: 24,
+        bits_per_pixel: 32,
+        byte_order: if cfg!(target_endian = "little") {
+            ByteOrder::LittleEndian
+        } else {
+            ByteOrder::BigEndian
+        },
+    };
+
+    /// Turns a color into a pixel value in this format, the opposite of `decode()`
+    /// # Examples
+    /// ```
+    /// use undici::{color::RGB, x11::image::PixelFormat};
+    ///
+    /// let pixel = PixelFormat::RGB24.encode(RGB { r: 0x12, g: 0x34, b: 0x56 });
+    /// assert_eq!(pixel, 0x123456);
+    /// ```
+    pub fn encode(&self, color: RGB) -> u64 {
+        encode_channel(color.r, self.red_mask)
+            | encode_channel(color.g, self.green_mask)
+            | encode_channel(color.b, self.blue_mask)
+    }
+
+    /// Turns a pixel value in this format into its color
+    /// # Examples
+    /// ```
+    /// use undici::x11::image::{ByteOrder, PixelFormat};
+    ///
+    /// // A 16 bit visual, 5 bits of red, 6 of green and 5 of blue
+    /// let rgb565 = PixelFormat {
+    ///     red_mask: 0xF800,
+    ///     green_mask: 0x07E0,
+    ///     blue_mask: 0x001F,
+    ///     depth: 16,
+    ///     bits_per_pixel: 16,
+    ///     byte_order: ByteOrder::LittleEndian,
+    /// };
+    ///
+    /// let orange = rgb565.decode(0xFC00);
+    /// assert_eq!((orange.r, orange.g, orange.b), (255, 130, 0));
+    ///
+    /// // A 30 bit deep colour visual, 10 bits per channel
+    /// let rgb101010 = PixelFormat {
+    ///     red_mask: 0x3FF0_0000,
+    ///     green_mask: 0x000F_FC00,
+    ///     blue_mask: 0x0000_03FF,
+    ///     depth: 30,
+    ///     bits_per_pixel: 32,
+    ///     byte_order: ByteOrder::LittleEndian,
+    /// };
+    ///
+    /// let white = rgb101010.decode(0x3FFF_FFFF);
+    /// assert_eq!((white.r, white.g, white.b), (255, 255, 255));
+    /// ```
+    pub fn decode(&self, pixel: u64) -> RGB {
+        RGB {
+            r: decode_channel(pixel, self.red_mask),
+            g: decode_channel(pixel, self.green_mask),
+            b: decode_channel(pixel, self.blue_mask),
+        }
+    }
 }
 
 impl Image {
@@ -29,20 +161,352 @@ impl Image {
     ///     first_pixel.g
     /// );
     /// ```
+    /// # Panics
+    /// If `position` is outside of the image
     pub fn get_pixel(&self, position: Vector2<i32>) -> RGB {
-        let pixel = unsafe { XGetPixel(self.image, position.x, position.y) };
+        self.assert_contains(&position);
 
-        RGB {
-            r: ((pixel >> 16) & 0xFF) as u8,
-            g: ((pixel >> 8) & 0xFF) as u8,
-            b: (pixel & 0xFF) as u8,
+        self.format
+            .decode(self.pixel_value(position.x as u32, position.y as u32))
+    }
+
+    /// Creates an image from `rgb`, which has 3 bytes (red, green and blue) for every
+    /// pixel, row by row. The image is in the `PixelFormat::RGB24` format, `put_image()`
+    /// converts it if the window uses a different one
+    /// # Examples
+    /// ```
+    /// use undici::x11::{common::Vector2, image::Image};
+    ///
+    /// // A 2x1 image, a red pixel and a blue one
+    /// let image = Image::from_rgb(2, 1, &[255, 0, 0, 0, 0, 255]);
+    ///
+    /// let blue = image.get_pixel(Vector2::new(1, 0));
+    /// assert_eq!((blue.r, blue.g, blue.b), (0, 0, 255));
+    /// ```
+    /// # Panics
+    /// If `rgb` is not exactly `width * height * 3` bytes long
+    pub fn from_rgb(width: u32, height: u32, rgb: &[u8]) -> Image {
+        assert_eq!(
+            rgb.len(),
+            width as usize * height as usize * 3,
+            "the buffer should have 3 bytes per pixel"
+        );
+
+        let image = Self::new_rgb24(width, height);
+
+        for (index, pixel) in rgb.chunks_exact(3).enumerate() {
+            let pixel = RGB {
+                r: pixel[0],
+                g: pixel[1],
+                b: pixel[2],
+            };
+
+            let x = index as u32 % width;
+            let y = index as u32 / width;
+
+            unsafe { XPutPixel(image.image, x as i32, y as i32, image.format.encode(pixel)) };
+        }
+
+        image
+    }
+
+    /// A black image in the `PixelFormat::RGB24` format, which doesn't need a display
+    fn new_rgb24(width: u32, height: u32) -> Image {
+        let format = PixelFormat::RGB24;
+        let byte_order = match format.byte_order {
+            ByteOrder::LittleEndian => LSBFirst,
+            ByteOrder::BigEndian => MSBFirst,
+        };
+
+        // `XDestroyImage` frees both the struct and the data with `free()`, so they have to
+        // come from `malloc()`
+        unsafe {
+            let image = libc::calloc(1, size_of::<XImage>()) as *mut XImage;
+            let data = libc::calloc(width.max(1) as usize * height.max(1) as usize, 4);
+
+            assert!(
+                !image.is_null() && !data.is_null(),
+                "could not allocate the image"
+            );
+
+            let ximage = &mut *image;
+            ximage.width = width as i32;
+            ximage.height = height as i32;
+            ximage.format = ZPixmap;
+            ximage.data = data as *mut _;
+            ximage.byte_order = byte_order;
+            ximage.bitmap_unit = 32;
+            ximage.bitmap_bit_order = byte_order;
+            ximage.bitmap_pad = 32;
+            ximage.depth = format.depth as i32;
+            ximage.bytes_per_line = width as i32 * 4;
+            ximage.bits_per_pixel = format.bits_per_pixel as i32;
+            ximage.red_mask = format.red_mask;
+            ximage.green_mask = format.green_mask;
+            ximage.blue_mask = format.blue_mask;
+
+            // This fills in the functions `XGetPixel` and `XPutPixel` use
+            XInitImage(image);
+
+            Image::from_ptr(image)
         }
     }
+
+    /// Changes the color of a pixel
+    /// # Examples
+    /// ```
+    /// use undici::{color::RGB, x11::{common::Vector2, image::Image}};
+    ///
+    /// let mut image = Image::from_rgb(1, 1, &[0, 0, 0]);
+    /// image.set_pixel(Vector2::new(0, 0), RGB { r: 10, g: 20, b: 30 });
+    ///
+    /// let pixel = image.get_pixel(Vector2::new(0, 0));
+    /// assert_eq!((pixel.r, pixel.g, pixel.b), (10, 20, 30));
+    /// ```
+    /// # Panics
+    /// If `position` is outside of the image
+    pub fn set_pixel(&mut self, position: Vector2<i32>, color: RGB) {
+        self.assert_contains(&position);
+
+        unsafe {
+            XPutPixel(
+                self.image,
+                position.x,
+                position.y,
+                self.format.encode(color),
+            )
+        };
+    }
+
+    /// How many bytes a row takes in memory, rows can be padded so this can be more than
+    /// `width * bits_per_pixel / 8`
+    pub fn stride(&self) -> usize {
+        unsafe { (*self.image).bytes_per_line as usize }
+    }
+
+    /// The raw pixel data, `height` rows of `stride()` bytes each, in the image's
+    /// `format`. This doesn't copy anything
+    pub fn as_bytes(&self) -> &[u8] {
+        let data = unsafe { (*self.image).data } as *const u8;
+        let length = self.stride() * self.height as usize;
+
+        // Images without pixels don't always have a buffer
+        if data.is_null() || length == 0 {
+            return &[];
+        }
+
+        unsafe { std::slice::from_raw_parts(data, length) }
+    }
+
+    /// The raw data of every row, from top to bottom, without the padding at the end
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screenshot = display.get_root_window().get_image();
+    ///
+    /// let bytes_per_row = (screenshot.width * screenshot.format.bits_per_pixel).div_ceil(8);
+    ///
+    /// for row in screenshot.rows() {
+    ///     assert_eq!(row.len(), bytes_per_row as usize);
+    /// }
+    /// ```
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        let row_length = (self.width as usize * self.format.bits_per_pixel as usize).div_ceil(8);
+
+        self.as_bytes()
+            .chunks_exact(self.stride().max(1))
+            .map(move |row| &row[..row_length])
+    }
+
+    /// Every pixel of the image, row by row, from the top left corner
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let screenshot = display.get_root_window().get_image();
+    ///
+    /// let brightest = screenshot
+    ///     .pixels()
+    ///     .map(|pixel| pixel.r as u32 + pixel.g as u32 + pixel.b as u32)
+    ///     .max();
+    /// ```
+    pub fn pixels(&self) -> impl Iterator<Item = RGB> + '_ {
+        let bytes_per_pixel = self.bytes_per_pixel();
+
+        // Decoding straight from the rows is a lot faster, pixels that don't take whole
+        // bytes are rare enough that asking Xlib for each of them is fine
+        let rows = bytes_per_pixel.map(|bytes_per_pixel| {
+            self.rows().flat_map(move |row| {
+                row.chunks_exact(bytes_per_pixel)
+                    .map(|bytes| self.format.decode(self.read_value(bytes)))
+            })
+        });
+
+        let unaligned = bytes_per_pixel.is_none().then(|| {
+            (0..self.height).flat_map(move |y| {
+                (0..self.width).map(move |x| {
+                    self.format
+                        .decode(unsafe { XGetPixel(self.image, x as i32, y as i32) })
+                })
+            })
+        });
+
+        rows.into_iter()
+            .flatten()
+            .chain(unaligned.into_iter().flatten())
+    }
+
+    /// Every pixel as 3 bytes, red, green and blue, row by row without any padding. This
+    /// is what most image libraries expect
+    pub fn to_rgb_buffer(&self) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(self.width as usize * self.height as usize * 3);
+
+        for pixel in self.pixels() {
+            buffer.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+
+        buffer
+    }
+
+    fn assert_contains(&self, position: &Vector2<i32>) {
+        assert!(
+            (0..self.width as i32).contains(&position.x)
+                && (0..self.height as i32).contains(&position.y),
+            "the pixel should be inside of the image"
+        );
+    }
+
+    /// The raw value of a pixel, read straight from memory when pixels take whole bytes,
+    /// which is much faster than asking Xlib
+    fn pixel_value(&self, x: u32, y: u32) -> u64 {
+        let Some(bytes_per_pixel) = self.bytes_per_pixel() else {
+            return unsafe { XGetPixel(self.image, x as i32, y as i32) };
+        };
+
+        let start = y as usize * self.stride() + x as usize * bytes_per_pixel;
+
+        self.read_value(&self.as_bytes()[start..start + bytes_per_pixel])
+    }
+
+    /// The color of pixel `x` of `row`, which is row `y` of `rows()`, for code that only
+    /// needs some of the pixels without going through `get_pixel()` for each of them
+    pub(crate) fn row_pixel(&self, row: &[u8], x: u32, y: u32) -> RGB {
+        let value = match self.bytes_per_pixel() {
+            Some(bytes_per_pixel) => {
+                let start = x as usize * bytes_per_pixel;
+
+                self.read_value(&row[start..start + bytes_per_pixel])
+            }
+            None => unsafe { XGetPixel(self.image, x as i32, y as i32) },
+        };
+
+        self.format.decode(value)
+    }
+
+    /// `None` when pixels don't take a whole number of bytes
+    fn bytes_per_pixel(&self) -> Option<usize> {
+        match self.format.bits_per_pixel {
+            8 => Some(1),
+            16 => Some(2),
+            24 => Some(3),
+            32 => Some(4),
+            _ => None,
+        }
+    }
+
+    /// The value of the pixel stored in `bytes`, in the image's byte order
+    fn read_value(&self, bytes: &[u8]) -> u64 {
+        match self.format.byte_order {
+            ByteOrder::LittleEndian => bytes
+                .iter()
+                .rev()
+                .fold(0, |pixel, byte| pixel << 8 | *byte as u64),
+            ByteOrder::BigEndian => bytes
+                .iter()
+                .fold(0, |pixel, byte| pixel << 8 | *byte as u64),
+        }
+    }
+
+    /// # Safety
+    /// `image` has to point to a valid `XImage`, which the `Image` takes ownership of
+    #[allow(non_upper_case_globals)]
+    pub(crate) unsafe fn from_ptr(image: *mut XImage) -> Self {
+        let ximage = &*image;
+
+        let byte_order = match ximage.byte_order {
+            LSBFirst => ByteOrder::LittleEndian,
+            _ => ByteOrder::BigEndian,
+        };
+
+        Self {
+            image,
+            width: ximage.width as u32,
+            height: ximage.height as u32,
+            format: PixelFormat {
+                red_mask: ximage.red_mask,
+                green_mask: ximage.green_mask,
+                blue_mask: ximage.blue_mask,
+                depth: ximage.depth as u32,
+                bits_per_pixel: ximage.bits_per_pixel as u32,
+                byte_order,
+            },
+        }
+    }
+
+    /// Pixmaps have no visual, so X sends their images without color masks and every
+    /// pixel would decode to black. This takes the masks from a `TrueColor` visual of the
+    /// same depth on the default screen instead, which is what the pixels were drawn with
+    pub(crate) fn fill_missing_masks(&mut self, display: *mut _XDisplay) {
+        if self.format.red_mask | self.format.green_mask | self.format.blue_mask != 0 {
+            return;
+        }
+
+        let mut info: XVisualInfo = unsafe { std::mem::zeroed() };
+        let found = unsafe {
+            XMatchVisualInfo(
+                display,
+                XDefaultScreen(display),
+                self.format.depth as i32,
+                TrueColor,
+                &mut info,
+            )
+        };
+
+        if found == 0 {
+            return;
+        }
+
+        self.format.red_mask = info.red_mask;
+        self.format.green_mask = info.green_mask;
+        self.format.blue_mask = info.blue_mask;
+
+        let ximage = unsafe { &mut *self.image };
+        ximage.red_mask = info.red_mask;
+        ximage.green_mask = info.green_mask;
+        ximage.blue_mask = info.blue_mask;
+    }
+
+    /// Like `from_ptr`, but `image` can also be null, which is what `XGetImage` returns
+    /// when Xlib could not read or store the reply even though the server sent no error
+    /// # Safety
+    /// `image` has to be null or point to a valid `XImage`
+    pub(crate) unsafe fn from_nullable_ptr(image: *mut XImage) -> Result<Self, ImageError> {
+        if image.is_null() {
+            return Err(ImageError::NullImage);
+        }
+
+        Ok(Self::from_ptr(image))
+    }
 }
 
+// These are also in `Drawable`, they are here too so they work without importing it
 impl Window {
     /// Takes a "picture" of the window, examples uses include:
     /// - Getting a specific pixel color
+    ///
     /// ... and for now, that's it. I just used it for my `qmk-ambience` project
     /// # Examples
     /// ```
@@ -53,26 +517,194 @@ impl Window {
     ///
     /// let screenshot = root_window.get_image();
     /// ```
+    /// # Panics
+    /// If X can't give us the image, for example because the window is not viewable, use
+    /// `try_get_image()` to get an error instead
     pub fn get_image(&self) -> Image {
-        let window_data = self.get_data();
-        let image = unsafe {
-            XGetImage(
-                self.display,
-                self.id,
+        Drawable::get_image(self)
+    }
+
+    /// Like `get_image()`, but only of a part of the window, `region` is relative to the
+    /// window and has to be inside of it
+    /// # Examples
+    /// ```
+    /// use undici::x11::{common::Rect, display::Display};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// let top_left = root_window.get_image_region(Rect::new(0, 0, 100, 50));
+    /// assert_eq!(top_left.width, 100);
+    /// assert_eq!(top_left.height, 50);
+    /// ```
+    /// # Panics
+    /// Same as `get_image()`, use `try_get_image_region()` to get an error instead
+    pub fn get_image_region(&self, region: Rect) -> Image {
+        Drawable::get_image_region(self, region)
+    }
+
+    /// Like `get_image`, but reports errors instead of handing out a broken `Image`, for
+    /// example a `BadMatch` when the window is not viewable
+    pub fn try_get_image(&self) -> Result<Image, ImageError> {
+        Drawable::try_get_image(self)
+    }
+
+    /// Like `get_image_region`, but reports errors, for example a `BadMatch` when the
+    /// region goes outside of the window
+    pub fn try_get_image_region(&self, region: Rect) -> Result<Image, ImageError> {
+        Drawable::try_get_image_region(self, region)
+    }
+}
+
+impl Image {
+    /// A copy of the `source` part of the image, in the pixel format of a window with
+    /// `attributes`. Xlib picks the bits per pixel the server wants for that depth. The
+    /// pixels of `source` that are outside of the image are left black
+    fn convert(
+        &self,
+        display: *mut _XDisplay,
+        source: Rect,
+        attributes: &XWindowAttributes,
+    ) -> Option<Image> {
+        let ximage = unsafe {
+            XCreateImage(
+                display,
+                attributes.visual,
+                attributes.depth as u32,
+                ZPixmap,
                 0,
+                null_mut(),
+                source.width,
+                source.height,
+                32,
                 0,
-                window_data.scale.x as u32,
-                window_data.scale.y as u32,
-                XAllPlanes(),
-                ZPixmap,
             )
         };
 
-        Image {
-            image,
-            width: window_data.scale.x as u32,
-            height: window_data.scale.y as u32,
+        if ximage.is_null() {
+            return None;
+        }
+
+        unsafe {
+            (*ximage).data = libc::calloc(
+                (*ximage).bytes_per_line.max(1) as usize,
+                source.height.max(1) as usize,
+            ) as *mut _;
+        }
+
+        // From here on the `Image` frees it, even if we return early
+        let mut converted = unsafe { Image::from_ptr(ximage) };
+
+        if unsafe { (*ximage).data.is_null() } {
+            return None;
         }
+
+        for y in 0..source.height as i32 {
+            for x in 0..source.width as i32 {
+                let position = Vector2::new(source.x + x, source.y + y);
+
+                if (0..self.width as i32).contains(&position.x)
+                    && (0..self.height as i32).contains(&position.y)
+                {
+                    converted.set_pixel(Vector2::new(x, y), self.get_pixel(position));
+                }
+            }
+        }
+
+        Some(converted)
+    }
+}
+
+impl Window {
+    /// Draws the `source` part of `image` on the window with `gc`, with its top left corner
+    /// at `destination`. If the image has a different pixel format than the window it's
+    /// converted first. Errors are ignored, use `try_put_image()` to get them
+    /// # Examples
+    /// ```
+    /// use undici::x11::{
+    ///     common::{Rect, Vector2},
+    ///     display::Display,
+    ///     image::Image,
+    /// };
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let window = display
+    ///     .create_window()
+    ///     .scale(Vector2::new(64, 64))
+    ///     .build()
+    ///     .expect("could not create window");
+    ///
+    /// window.map();
+    ///
+    /// let gc = display.create_gc(&window);
+    /// let gray = Image::from_rgb(32, 32, &[128; 32 * 32 * 3]);
+    /// window.put_image(&gc, &gray, Rect::new(0, 0, 32, 32), Vector2::new(16, 16));
+    /// ```
+    pub fn put_image(&self, gc: &GC, image: &Image, source: Rect, destination: Vector2<i32>) {
+        if let Ok(attributes) = self.try_get_attributes() {
+            self.put_image_with(&attributes, gc, image, source, destination);
+        }
+    }
+
+    /// Like `put_image`, but reports errors, for example a `BadMatch` if `source` goes
+    /// outside of an image that has the same format as the window
+    pub fn try_put_image(
+        &self,
+        gc: &GC,
+        image: &Image,
+        source: Rect,
+        destination: Vector2<i32>,
+    ) -> Result<(), ProtocolError> {
+        let attributes = self.try_get_attributes()?;
+
+        checked(self.display, || {
+            self.put_image_with(&attributes, gc, image, source, destination)
+        })
+    }
+
+    fn put_image_with(
+        &self,
+        attributes: &XWindowAttributes,
+        gc: &GC,
+        image: &Image,
+        source: Rect,
+        destination: Vector2<i32>,
+    ) {
+        let visual = unsafe { &*attributes.visual };
+        let same_format = image.format.depth == attributes.depth as u32
+            && image.format.red_mask == visual.red_mask
+            && image.format.green_mask == visual.green_mask
+            && image.format.blue_mask == visual.blue_mask;
+
+        // X doesn't convert images, the server only takes them in the window's format
+        let converted = if same_format {
+            None
+        } else {
+            match image.convert(self.display, source, attributes) {
+                Some(converted) => Some(converted),
+                None => return,
+            }
+        };
+
+        let (ximage, source_position) = match &converted {
+            Some(converted) => (converted.image, Vector2::new(0, 0)),
+            None => (image.image, Vector2::new(source.x, source.y)),
+        };
+
+        unsafe {
+            XPutImage(
+                self.display,
+                self.id,
+                gc.gc,
+                ximage,
+                source_position.x,
+                source_position.y,
+                destination.x,
+                destination.y,
+                source.width,
+                source.height,
+            )
+        };
     }
 }
 
@@ -0,0 +1,226 @@
+use super::window::Modifiers;
+use std::{
+    ffi::{c_char, c_uint, CStr, CString},
+    sync::Mutex,
+};
+use x11::xlib::{
+    _XDisplay, AnyModifier, ControlMask, LockMask, Mod1Mask, Mod4Mask, ShiftMask, XFreeModifiermap,
+    XGetModifierMapping, XKeycodeToKeysym, XKeysymToString, XStringToKeysym,
+};
+
+/// Keysyms with this prefix are just a Unicode code point, for characters that don't have
+/// a keysym of their own
+const UNICODE_KEYSYM_PREFIX: u64 = 0x0100_0000;
+
+/// A symbol on a key, like `a`, `A`, `Return` or `F1`. The same key gives different
+/// keysyms depending on the modifiers and the layout, `a` and `A` are two different
+/// keysyms
+/// # Examples
+/// ```
+/// use undici::x11::keyboard::Keysym;
+///
+/// let enter = Keysym::from_name("Return").unwrap();
+/// assert_eq!(enter.name().unwrap(), "Return");
+///
+/// assert_ne!(Keysym::from_name("a"), Keysym::from_name("A"));
+/// assert!(Keysym::from_name("not a key").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Keysym(pub(crate) u64);
+
+impl Keysym {
+    /// Accepts the names `xev` shows, `None` if there is no keysym with that name
+    pub fn from_name(name: &str) -> Option<Keysym> {
+        let name_c = CString::new(name).ok()?;
+        let keysym = unsafe { XStringToKeysym(name_c.as_ptr()) };
+
+        (keysym != 0).then_some(Keysym(keysym))
+    }
+
+    /// Not every keysym has a name, especially the ones of unusual layouts
+    pub fn name(&self) -> Option<String> {
+        let name_c: *mut c_char = unsafe { XKeysymToString(self.0) };
+
+        if name_c.is_null() {
+            return None;
+        }
+
+        Some(
+            unsafe { CStr::from_ptr(name_c) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// The character this keysym stands for, only if it's one of the keysyms that are
+    /// just a Unicode code point
+    pub(crate) fn unicode_char(&self) -> Option<char> {
+        if self.0 & 0xFF00_0000 != UNICODE_KEYSYM_PREFIX {
+            return None;
+        }
+
+        char::from_u32((self.0 & 0x00FF_FFFF) as u32)
+    }
+
+    /// The raw value of the keysym, as defined in `X11/keysymdef.h`
+    pub fn id(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The modifier map of every display we asked for one, keyed by the address of the
+/// display. The map can only change with a `MappingNotify` event, so there is no point in
+/// asking the server again for every grab and every key event
+static MODIFIER_MAPS: Mutex<Vec<(usize, ModifierMap)>> = Mutex::new(Vec::new());
+
+/// Which of the eight X modifier bits each `Modifiers` flag ended up on. Shift, Lock and
+/// Control are fixed by the protocol, but Alt, NumLock, Super and ScrollLock can be on any
+/// of `Mod1` to `Mod5` depending on the keyboard layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ModifierMap {
+    alt: c_uint,
+    num: c_uint,
+    super_: c_uint,
+    scroll_lock: c_uint,
+}
+
+impl ModifierMap {
+    /// The cached map of `display`, fetched from the server the first time
+    pub(crate) fn get(display: *mut _XDisplay) -> ModifierMap {
+        let mut maps = MODIFIER_MAPS.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((_, map)) = maps
+            .iter()
+            .find(|(map_display, _)| *map_display == display as usize)
+        {
+            return *map;
+        }
+
+        let map = Self::query(display);
+        maps.push((display as usize, map));
+
+        map
+    }
+
+    /// Throws away the cached map of `display`, it will be fetched again when needed
+    pub(crate) fn forget(display: *mut _XDisplay) {
+        let mut maps = MODIFIER_MAPS.lock().unwrap_or_else(|e| e.into_inner());
+        maps.retain(|(map_display, _)| *map_display != display as usize);
+    }
+
+    fn query(display: *mut _XDisplay) -> ModifierMap {
+        // Pretty much every layout puts Alt on Mod1 and Super on Mod4, so if we can't find
+        // them we guess those. The locks are only set if the keys actually exist, a wrong
+        // guess would make every grab register useless lock variants
+        let mut map = ModifierMap {
+            alt: 0,
+            num: 0,
+            super_: 0,
+            scroll_lock: 0,
+        };
+
+        let xmodmap = unsafe { XGetModifierMapping(display) };
+        if let Some(xmodmap_ref) = unsafe { xmodmap.as_ref() } {
+            let keys_per_modifier = xmodmap_ref.max_keypermod.max(0) as usize;
+
+            // The first three are Shift, Lock and Control, which are always the same
+            for index in 3..8 {
+                let mask = 1 << index;
+
+                for key in 0..keys_per_modifier {
+                    let keycode =
+                        unsafe { *xmodmap_ref.modifiermap.add(index * keys_per_modifier + key) };
+
+                    if keycode == 0 {
+                        continue;
+                    }
+
+                    let keysym = Keysym(unsafe { XKeycodeToKeysym(display, keycode, 0) });
+                    let slot = match keysym.name().as_deref() {
+                        Some("Alt_L" | "Alt_R" | "Meta_L" | "Meta_R") => &mut map.alt,
+                        Some("Num_Lock") => &mut map.num,
+                        Some("Super_L" | "Super_R") => &mut map.super_,
+                        Some("Scroll_Lock") => &mut map.scroll_lock,
+                        _ => continue,
+                    };
+
+                    if *slot == 0 {
+                        *slot = mask;
+                    }
+                }
+            }
+
+            unsafe { XFreeModifiermap(xmodmap) };
+        }
+
+        if map.alt == 0 {
+            map.alt = Mod1Mask;
+        }
+
+        if map.super_ == 0 {
+            map.super_ = Mod4Mask;
+        }
+
+        map
+    }
+
+    /// The X modifier mask for `modifiers`. A modifier whose key is not on the keyboard
+    /// (like NumLock on most laptops) has no bit, so it's silently left out, and a grab
+    /// that asked for it fires without it. Nobody can press a key that isn't there, so
+    /// that's closer to what was asked than failing the whole grab
+    pub(crate) fn mask_of(self, modifiers: Modifiers) -> c_uint {
+        if modifiers.contains(Modifiers::ANY) {
+            return AnyModifier;
+        }
+
+        self.flags()
+            .into_iter()
+            .filter(|(flag, _)| modifiers.contains(*flag))
+            .fold(0, |mask, (_, bit)| mask | bit)
+    }
+
+    /// The opposite of `mask_of()`, turns the `state` of an event into the modifiers that
+    /// were held
+    pub(crate) fn modifiers_of(self, state: c_uint) -> Modifiers {
+        self.flags()
+            .into_iter()
+            .filter(|(_, bit)| *bit != 0 && state & bit != 0)
+            .fold(Modifiers::NONE, |modifiers, (flag, _)| modifiers | flag)
+    }
+
+    /// Every combination of the lock modifiers that are not already in `mask`, including
+    /// none of them. A passive grab only fires with the exact modifiers it was registered
+    /// with, so we register one for each combination to make grabs work with NumLock on
+    pub(crate) fn lock_variants(self, mask: c_uint) -> Vec<c_uint> {
+        if mask == AnyModifier {
+            return vec![mask];
+        }
+
+        let locks: Vec<c_uint> = [LockMask, self.num, self.scroll_lock]
+            .into_iter()
+            .filter(|lock| *lock != 0 && mask & lock == 0)
+            .collect();
+
+        (0..1 << locks.len())
+            .map(|combination: usize| {
+                locks
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| combination & (1 << index) != 0)
+                    .fold(mask, |mask, (_, lock)| mask | lock)
+            })
+            .collect()
+    }
+
+    fn flags(self) -> [(Modifiers, c_uint); 7] {
+        [
+            (Modifiers::SHIFT, ShiftMask),
+            (Modifiers::LOCK, LockMask),
+            (Modifiers::CONTROL, ControlMask),
+            (Modifiers::ALT, self.alt),
+            (Modifiers::NUM, self.num),
+            (Modifiers::SUPER, self.super_),
+            (Modifiers::SCROLL_LOCK, self.scroll_lock),
+        ]
+    }
+}
@@ -1,7 +1,22 @@
+pub mod atom;
+pub mod builder;
+pub mod colormap;
 pub mod common;
 pub mod cursor;
 pub mod display;
+pub mod drawable;
+pub mod encode;
 pub mod errors;
 pub mod events;
+pub mod gc;
+pub mod grab;
 pub mod image;
+pub mod keyboard;
+pub mod pixmap;
+pub mod property;
+pub mod screen;
+
+#[cfg(feature = "shm")]
+pub mod shm;
+pub mod tree;
 pub mod window;
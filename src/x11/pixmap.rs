@@ -0,0 +1,54 @@
+use super::{
+    common::Vector2,
+    display::Display,
+    errors::{checked, ProtocolError},
+};
+use x11::xlib::{XCreatePixmap, XFreePixmap};
+
+/// An image that lives on the X server, it can be drawn on like a window but it's never
+/// shown, its content can be copied to a window with `Drawable`'s `.copy_area()`. It's
+/// freed when dropped, so it can't outlive its `Display`. Can be created with `Display`'s
+/// `.create_pixmap()` method
+pub struct Pixmap<'a> {
+    pub(crate) id: u64,
+    pub(crate) display: &'a Display,
+    pub scale: Vector2<u32>,
+    pub depth: u32,
+}
+
+impl Display {
+    /// Creates a pixmap on the default screen, its content starts out undefined, so
+    /// draw on all of it before using it. `depth` has to be one the screen supports, to
+    /// copy it to a window use the same depth as the window, usually `Screen.depth`
+    pub fn create_pixmap(
+        &self,
+        scale: Vector2<u32>,
+        depth: u32,
+    ) -> Result<Pixmap<'_>, ProtocolError> {
+        let root_window = self.get_root_window();
+
+        let id = checked(self.display, || unsafe {
+            XCreatePixmap(self.display, root_window.id, scale.x, scale.y, depth)
+        })?;
+
+        Ok(Pixmap {
+            id,
+            display: self,
+            scale,
+            depth,
+        })
+    }
+}
+
+impl Pixmap<'_> {
+    /// The raw id of the pixmap
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Drop for Pixmap<'_> {
+    fn drop(&mut self) {
+        unsafe { XFreePixmap(self.display.display, self.id) };
+    }
+}
@@ -0,0 +1,416 @@
+use super::{
+    atom::Atom,
+    errors::{checked, checked_reply, PropertyError, ProtocolError},
+    window::Window,
+};
+use std::ffi::{c_int, c_long, c_short, c_uchar, c_void};
+use x11::xlib::*;
+
+/// How many 32 bit units we ask for per request, bigger properties (icons for example)
+/// are read in multiple pieces
+const CHUNK_LENGTH: c_long = 1 << 16;
+
+/// The data of a property, X stores properties as lists of 8, 16 or 32 bit values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyValue {
+    Format8(Vec<u8>),
+    Format16(Vec<u16>),
+    Format32(Vec<u32>),
+}
+
+/// A property read from a window, see `Window`'s `.get_property()` method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    /// What the data means, for example `Atom::STRING`, `Atom::CARDINAL` or `Atom::WINDOW`
+    pub type_: Atom,
+    pub value: PropertyValue,
+    display: *mut _XDisplay,
+}
+
+impl Property {
+    /// The data if the property has format 8
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.value {
+            PropertyValue::Format8(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// The data if the property has format 16
+    pub fn as_u16s(&self) -> Option<&[u16]> {
+        match &self.value {
+            PropertyValue::Format16(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The data if the property has format 32, for example a `CARDINAL` like `_NET_WM_PID`
+    pub fn as_u32s(&self) -> Option<&[u32]> {
+        match &self.value {
+            PropertyValue::Format32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Reads a format 8 property as text (`STRING`, `UTF8_STRING`...), stopping at the
+    /// first nul byte. Invalid UTF-8 is replaced, `STRING` is Latin-1, which is only the
+    /// same as UTF-8 for ASCII
+    pub fn text(&self) -> Option<String> {
+        let bytes = self.as_bytes()?;
+        let end = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(bytes.len());
+
+        Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    /// Reads a format 8 property as a list of nul separated strings, like `WM_CLASS` or
+    /// `WM_COMMAND`
+    pub fn strings(&self) -> Option<Vec<String>> {
+        let bytes = self.as_bytes()?;
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+
+        if bytes.is_empty() {
+            return Some(Vec::new());
+        }
+
+        Some(
+            bytes
+                .split(|byte| *byte == 0)
+                .map(|string| String::from_utf8_lossy(string).into_owned())
+                .collect(),
+        )
+    }
+
+    /// Reads a format 32 property as atoms, like `_NET_WM_STATE`
+    pub fn atoms(&self) -> Option<Vec<Atom>> {
+        Some(self.as_u32s()?.iter().map(|id| Atom(*id as u64)).collect())
+    }
+
+    /// Reads a format 32 property as windows, like `_NET_CLIENT_LIST` or
+    /// `WM_TRANSIENT_FOR`
+    pub fn windows(&self) -> Option<Vec<Window>> {
+        Some(
+            self.as_u32s()?
+                .iter()
+                .map(|id| Window {
+                    id: *id as u64,
+                    display: self.display,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl PropertyValue {
+    /// Returns `false` if `more` has a different format
+    fn extend(&mut self, more: PropertyValue) -> bool {
+        match (self, more) {
+            (PropertyValue::Format8(all), PropertyValue::Format8(more)) => all.extend(more),
+            (PropertyValue::Format16(all), PropertyValue::Format16(more)) => all.extend(more),
+            (PropertyValue::Format32(all), PropertyValue::Format32(more)) => all.extend(more),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(text: &str) -> Self {
+        PropertyValue::Format8(text.as_bytes().to_vec())
+    }
+}
+
+impl From<Vec<u8>> for PropertyValue {
+    fn from(bytes: Vec<u8>) -> Self {
+        PropertyValue::Format8(bytes)
+    }
+}
+
+impl From<Vec<u16>> for PropertyValue {
+    fn from(values: Vec<u16>) -> Self {
+        PropertyValue::Format16(values)
+    }
+}
+
+impl From<Vec<u32>> for PropertyValue {
+    fn from(values: Vec<u32>) -> Self {
+        PropertyValue::Format32(values)
+    }
+}
+
+impl From<&[Atom]> for PropertyValue {
+    fn from(atoms: &[Atom]) -> Self {
+        PropertyValue::Format32(atoms.iter().map(|atom| atom.0 as u32).collect())
+    }
+}
+
+impl From<&[Window]> for PropertyValue {
+    fn from(windows: &[Window]) -> Self {
+        PropertyValue::Format32(windows.iter().map(|window| window.id as u32).collect())
+    }
+}
+
+impl Window {
+    /// Reads a property of any type, `None` if the window doesn't have it
+    /// # Examples
+    /// ```
+    /// use undici::x11::{atom::Atom, display::Display};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let window = display.create_window().build().unwrap();
+    ///
+    /// window.set_property(Atom::WM_NAME, Atom::STRING, &"undici".into()).unwrap();
+    ///
+    /// let name = window.get_property(Atom::WM_NAME).unwrap().unwrap();
+    /// assert_eq!(name.type_, Atom::STRING);
+    /// assert_eq!(name.text().unwrap(), "undici");
+    /// ```
+    pub fn get_property(&self, property: Atom) -> Result<Option<Property>, PropertyError> {
+        self.read_property(property, AnyPropertyType as u64)
+    }
+
+    /// Reads a property, failing with `PropertyError::TypeMismatch` if it has a type
+    /// other than `type_`
+    /// # Examples
+    /// ```
+    /// use undici::x11::{atom::Atom, display::Display, errors::PropertyError};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let window = display.create_window().build().unwrap();
+    /// let net_wm_pid = display.intern_atom("_NET_WM_PID").unwrap();
+    ///
+    /// window
+    ///     .set_property(net_wm_pid, Atom::CARDINAL, &vec![1234u32].into())
+    ///     .unwrap();
+    ///
+    /// let pid = window.get_property_of_type(net_wm_pid, Atom::CARDINAL).unwrap().unwrap();
+    /// assert_eq!(pid.as_u32s(), Some(&[1234][..]));
+    ///
+    /// assert_eq!(
+    ///     window.get_property_of_type(net_wm_pid, Atom::STRING),
+    ///     Err(PropertyError::TypeMismatch {
+    ///         expected: Atom::STRING,
+    ///         found: Atom::CARDINAL
+    ///     })
+    /// );
+    /// ```
+    pub fn get_property_of_type(
+        &self,
+        property: Atom,
+        type_: Atom,
+    ) -> Result<Option<Property>, PropertyError> {
+        self.read_property(property, type_.0)
+    }
+
+    /// Replaces the property, creating it if it doesn't exist
+    pub fn set_property(
+        &self,
+        property: Atom,
+        type_: Atom,
+        value: &PropertyValue,
+    ) -> Result<(), ProtocolError> {
+        self.change_property(property, type_, value, PropModeReplace)
+    }
+
+    /// Adds `value` to the end of the property, which must have the same type and format
+    /// (or not exist), otherwise this fails with `BadMatch`
+    pub fn append_property(
+        &self,
+        property: Atom,
+        type_: Atom,
+        value: &PropertyValue,
+    ) -> Result<(), ProtocolError> {
+        self.change_property(property, type_, value, PropModeAppend)
+    }
+
+    /// Deleting a property that doesn't exist is not an error
+    pub fn delete_property(&self, property: Atom) -> Result<(), ProtocolError> {
+        checked(self.display, || unsafe {
+            XDeleteProperty(self.display, self.id, property.0)
+        })?;
+
+        Ok(())
+    }
+
+    /// All the properties the window has, use `Display`'s `.atom_name()` to get their names
+    pub fn list_properties(&self) -> Result<Vec<Atom>, ProtocolError> {
+        let mut count = 0;
+        let atoms_p = checked_reply(self.display, || unsafe {
+            XListProperties(self.display, self.id, &mut count)
+        })?;
+
+        if atoms_p.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let atoms = unsafe {
+            let atoms = std::slice::from_raw_parts(atoms_p, count as usize)
+                .iter()
+                .map(|atom| Atom(*atom))
+                .collect();
+
+            XFree(atoms_p as *mut c_void);
+            atoms
+        };
+
+        Ok(atoms)
+    }
+
+    fn change_property(
+        &self,
+        property: Atom,
+        type_: Atom,
+        value: &PropertyValue,
+        mode: c_int,
+    ) -> Result<(), ProtocolError> {
+        // Xlib wants format 16 and 32 data as arrays of `short` and `long`, even when
+        // `long` is 64 bits
+        let shorts: Vec<c_short>;
+        let longs: Vec<c_long>;
+
+        let (format, data, length) = match value {
+            PropertyValue::Format8(bytes) => (8, bytes.as_ptr(), bytes.len()),
+
+            PropertyValue::Format16(values) => {
+                shorts = values.iter().map(|value| *value as c_short).collect();
+                (16, shorts.as_ptr() as *const c_uchar, shorts.len())
+            }
+
+            PropertyValue::Format32(values) => {
+                longs = values.iter().map(|value| *value as c_long).collect();
+                (32, longs.as_ptr() as *const c_uchar, longs.len())
+            }
+        };
+
+        checked(self.display, || unsafe {
+            XChangeProperty(
+                self.display,
+                self.id,
+                property.0,
+                type_.0,
+                format,
+                mode,
+                data,
+                length as c_int,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    fn read_property(
+        &self,
+        property: Atom,
+        requested_type: u64,
+    ) -> Result<Option<Property>, PropertyError> {
+        'read: loop {
+            let mut value: Option<PropertyValue> = None;
+            let mut type_ = 0;
+            let mut offset = 0;
+
+            loop {
+                let mut actual_type = 0;
+                let mut format = 0;
+                let mut items = 0;
+                let mut bytes_after = 0;
+                let mut data: *mut c_uchar = std::ptr::null_mut();
+
+                checked_reply(self.display, || unsafe {
+                    XGetWindowProperty(
+                        self.display,
+                        self.id,
+                        property.0,
+                        offset,
+                        CHUNK_LENGTH,
+                        false.into(),
+                        requested_type,
+                        &mut actual_type,
+                        &mut format,
+                        &mut items,
+                        &mut bytes_after,
+                        &mut data,
+                    )
+                })?;
+
+                let chunk = unsafe { read_chunk(data, format, items as usize) };
+
+                if !data.is_null() {
+                    unsafe { XFree(data as *mut c_void) };
+                }
+
+                // The property doesn't exist
+                if actual_type == 0 {
+                    return Ok(None);
+                }
+
+                // When the type is wrong X tells us the real one, but sends no data
+                if requested_type != AnyPropertyType as u64 && actual_type != requested_type {
+                    return Err(PropertyError::TypeMismatch {
+                        expected: Atom(requested_type),
+                        found: Atom(actual_type),
+                    });
+                }
+
+                match (&mut value, chunk) {
+                    (None, chunk) => value = chunk,
+                    (Some(_), None) => {}
+
+                    // Someone replaced the property while we were reading it
+                    (Some(value), Some(chunk)) => {
+                        if !value.extend(chunk) {
+                            continue 'read;
+                        }
+                    }
+                }
+
+                if type_ != 0 && type_ != actual_type {
+                    continue 'read;
+                }
+                type_ = actual_type;
+
+                if bytes_after == 0 {
+                    break;
+                }
+
+                // The offset is in 32 bit units, no matter the format
+                offset += (items as c_long * format as c_long / 8) / 4;
+            }
+
+            return Ok(value.map(|value| Property {
+                type_: Atom(type_),
+                value,
+                display: self.display,
+            }));
+        }
+    }
+}
+
+/// # Safety
+/// `data` has to be what `XGetWindowProperty` returned for that format and item count
+unsafe fn read_chunk(data: *mut c_uchar, format: c_int, items: usize) -> Option<PropertyValue> {
+    if data.is_null() {
+        return None;
+    }
+
+    let value = match format {
+        8 => PropertyValue::Format8(std::slice::from_raw_parts(data, items).to_vec()),
+        16 => PropertyValue::Format16(
+            std::slice::from_raw_parts(data as *const c_short, items)
+                .iter()
+                .map(|value| *value as u16)
+                .collect(),
+        ),
+        32 => PropertyValue::Format32(
+            std::slice::from_raw_parts(data as *const c_long, items)
+                .iter()
+                .map(|value| *value as u32)
+                .collect(),
+        ),
+        _ => return None,
+    };
+
+    Some(value)
+}
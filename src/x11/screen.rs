@@ -0,0 +1,155 @@
+use super::{colormap::Colormap, common::Vector2, display::Display, window::Window};
+use x11::xlib::{self, XDefaultScreen, XScreenCount, XScreenOfDisplay};
+
+/// How the pixel values of a `Visual` are turned into colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisualClass {
+    StaticGray,
+    GrayScale,
+    StaticColor,
+    PseudoColor,
+    TrueColor,
+    DirectColor,
+}
+
+/// Describes how pixels are laid out on a screen (or on a window that uses this visual)
+#[derive(Clone, Copy, PartialEq)]
+pub struct Visual {
+    pub(crate) visual: *mut xlib::Visual,
+
+    /// The number of the screen the visual belongs to, it can't be used on other screens
+    pub screen: i32,
+    pub id: u64,
+    pub class: VisualClass,
+    pub red_mask: u64,
+    pub green_mask: u64,
+    pub blue_mask: u64,
+    pub bits_per_rgb: i32,
+}
+
+/// A single screen of a display. Most setups have just one, even with multiple monitors,
+/// because those are usually merged into a single big screen
+pub struct Screen {
+    /// The index of the screen, the `1` in `:0.1`
+    pub number: i32,
+    pub root_window: Window,
+
+    /// Size in pixels
+    pub size: Vector2<i32>,
+
+    /// Size in millimetres, as reported by the server, which is not always accurate
+    pub size_mm: Vector2<i32>,
+
+    pub depth: i32,
+    pub visual: Visual,
+    pub colormap: Colormap,
+
+    /// The pixel values for black and white in the default colormap
+    pub black_pixel: u64,
+    pub white_pixel: u64,
+}
+
+impl Visual {
+    /// # Safety
+    /// `visual` has to point to a valid Xlib `Visual` of the screen number `screen`
+    pub(crate) unsafe fn from_ptr(visual: *mut xlib::Visual, screen: i32) -> Self {
+        let xvisual = *visual;
+
+        let class = match xvisual.class {
+            xlib::StaticGray => VisualClass::StaticGray,
+            xlib::GrayScale => VisualClass::GrayScale,
+            xlib::StaticColor => VisualClass::StaticColor,
+            xlib::PseudoColor => VisualClass::PseudoColor,
+            xlib::DirectColor => VisualClass::DirectColor,
+            _ => VisualClass::TrueColor,
+        };
+
+        Self {
+            visual,
+            screen,
+            id: xvisual.visualid,
+            class,
+            red_mask: xvisual.red_mask,
+            green_mask: xvisual.green_mask,
+            blue_mask: xvisual.blue_mask,
+            bits_per_rgb: xvisual.bits_per_rgb,
+        }
+    }
+}
+
+impl Display {
+    /// How many screens this display has, they are numbered from `0` to `screen_count() - 1`
+    pub fn screen_count(&self) -> i32 {
+        unsafe { XScreenCount(self.display) }
+    }
+
+    /// Get information about a specific screen, returns `None` if there is no screen with
+    /// that number
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// let screen = display.screen(0).expect("every display has at least one screen");
+    /// assert_eq!(screen.number, 0);
+    /// assert!(display.screen(display.screen_count()).is_none());
+    /// ```
+    pub fn screen(&self, number: i32) -> Option<Screen> {
+        if number < 0 || number >= self.screen_count() {
+            return None;
+        }
+
+        let xscreen = unsafe { *XScreenOfDisplay(self.display, number) };
+
+        Some(Screen {
+            number,
+            root_window: Window {
+                id: xscreen.root,
+                display: self.display,
+            },
+            size: Vector2::new(xscreen.width, xscreen.height),
+            size_mm: Vector2::new(xscreen.mwidth, xscreen.mheight),
+            depth: xscreen.root_depth,
+            visual: unsafe { Visual::from_ptr(xscreen.root_visual, number) },
+            colormap: Colormap {
+                id: xscreen.cmap,
+                display: self.display,
+            },
+            black_pixel: xscreen.black_pixel,
+            white_pixel: xscreen.white_pixel,
+        })
+    }
+
+    /// The screen that was picked in the display name (the `1` in `:0.1`), or `0`
+    pub fn default_screen(&self) -> Screen {
+        let number = unsafe { XDefaultScreen(self.display) };
+
+        self.screen(number)
+            .expect("the default screen should always exist")
+    }
+
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    ///
+    /// for screen in display.screens() {
+    ///     println!(
+    ///         "Screen {}: {}x{} pixels, {}x{} mm, {} bit",
+    ///         screen.number,
+    ///         screen.size.x,
+    ///         screen.size.y,
+    ///         screen.size_mm.x,
+    ///         screen.size_mm.y,
+    ///         screen.depth,
+    ///     );
+    /// }
+    /// ```
+    pub fn screens(&self) -> Vec<Screen> {
+        (0..self.screen_count())
+            .filter_map(|number| self.screen(number))
+            .collect()
+    }
+}
@@ -0,0 +1,230 @@
+use super::{
+    common::Rect,
+    display::Display,
+    errors::{checked, checked_reply, ImageError, ProtocolError},
+    image::Image,
+    window::Window,
+};
+use std::ptr::null_mut;
+use x11::{
+    xlib::{XAllPlanes, XDestroyImage, XGetImage, XGetSubImage, XSync, ZPixmap},
+    xshm::{
+        XShmAttach, XShmCreateImage, XShmDetach, XShmGetImage, XShmQueryExtension, XShmSegmentInfo,
+    },
+};
+
+/// Captures the same region of a window over and over, reusing the same memory. With the
+/// MIT-SHM extension the X server writes the pixels straight into memory we share with
+/// it, instead of sending them over the socket, which is a lot faster for big regions.
+/// Can be created with `ShmCapture::new()`
+///
+/// Shared memory only works when the X server runs on the same machine, when it doesn't
+/// (or it doesn't have the extension) this falls back to normal requests, which are
+/// slower but still don't allocate a new image every frame
+pub struct ShmCapture<'a> {
+    display: &'a Display,
+    window: Window,
+    region: Rect,
+    image: Image,
+
+    /// `None` when we fell back to normal requests. Boxed because Xlib keeps a pointer to
+    /// it in the image
+    segment: Option<Box<XShmSegmentInfo>>,
+}
+
+impl<'a> ShmCapture<'a> {
+    /// Prepares the capture of `region` of `window`, which has to live on `display`. The
+    /// region has to stay inside of the window for as long as the capture is used
+    /// # Examples
+    /// ```
+    /// use undici::x11::{common::Rect, display::Display, shm::ShmCapture};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// let mut capture = ShmCapture::new(&display, root_window, Rect::new(0, 0, 64, 64))
+    ///     .expect("could not prepare the capture");
+    ///
+    /// for _ in 0..10 {
+    ///     let frame = capture.capture().expect("could not capture the screen");
+    ///     let first_pixel = frame.pixels().next().unwrap();
+    ///
+    ///     println!("{} {} {}", first_pixel.r, first_pixel.g, first_pixel.b);
+    /// }
+    /// ```
+    pub fn new(
+        display: &'a Display,
+        window: Window,
+        region: Rect,
+    ) -> Result<ShmCapture<'a>, ImageError> {
+        if let Some(capture) = Self::new_shared(display, window, region)? {
+            return Ok(capture);
+        }
+
+        let image = checked_reply(display.display, || unsafe {
+            XGetImage(
+                display.display,
+                window.id,
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                XAllPlanes(),
+                ZPixmap,
+            )
+        })?;
+
+        Ok(ShmCapture {
+            display,
+            window,
+            region,
+            image: unsafe { Image::from_nullable_ptr(image)? },
+            segment: None,
+        })
+    }
+
+    /// `Ok(None)` when shared memory can't be used
+    fn new_shared(
+        owner: &'a Display,
+        window: Window,
+        region: Rect,
+    ) -> Result<Option<ShmCapture<'a>>, ProtocolError> {
+        let display = owner.display;
+
+        if unsafe { XShmQueryExtension(display) } == 0 {
+            return Ok(None);
+        }
+
+        let attributes = window.try_get_attributes()?;
+        let mut segment = Box::new(XShmSegmentInfo {
+            shmseg: 0,
+            shmid: -1,
+            shmaddr: null_mut(),
+            readOnly: false.into(),
+        });
+
+        let image = unsafe {
+            XShmCreateImage(
+                display,
+                attributes.visual,
+                attributes.depth as u32,
+                ZPixmap,
+                null_mut(),
+                &mut *segment,
+                region.width,
+                region.height,
+            )
+        };
+
+        if image.is_null() {
+            return Ok(None);
+        }
+
+        let size = unsafe { (*image).bytes_per_line as usize * (*image).height as usize };
+        let shmid = unsafe { libc::shmget(libc::IPC_PRIVATE, size, libc::IPC_CREAT | 0o600) };
+
+        if shmid < 0 {
+            unsafe { XDestroyImage(image) };
+            return Ok(None);
+        }
+
+        let address = unsafe { libc::shmat(shmid, null_mut(), 0) };
+
+        // The segment only really goes away once everyone detached from it, so marking it
+        // for removal right away makes sure it doesn't outlive us, even if we crash
+        unsafe { libc::shmctl(shmid, libc::IPC_RMID, null_mut()) };
+
+        if address as isize == -1 {
+            unsafe { XDestroyImage(image) };
+            return Ok(None);
+        }
+
+        segment.shmid = shmid;
+        segment.shmaddr = address as *mut _;
+        unsafe { (*image).data = address as *mut _ };
+
+        // Servers on other machines can still say they have the extension, but they fail
+        // to attach to a segment that isn't there
+        let attached = checked(display, || unsafe { XShmAttach(display, &mut *segment) });
+
+        if !matches!(attached, Ok(status) if status != 0) {
+            unsafe {
+                (*image).data = null_mut();
+                XDestroyImage(image);
+                libc::shmdt(address);
+            }
+
+            return Ok(None);
+        }
+
+        Ok(Some(ShmCapture {
+            display: owner,
+            window,
+            region,
+            image: unsafe { Image::from_ptr(image) },
+            segment: Some(segment),
+        }))
+    }
+
+    /// Whether the capture uses shared memory, or it fell back to normal requests
+    pub fn is_shared(&self) -> bool {
+        self.segment.is_some()
+    }
+
+    /// Captures the region again, the returned image is overwritten by the next capture
+    pub fn capture(&mut self) -> Result<&Image, ProtocolError> {
+        let display = self.display.display;
+
+        match self.segment {
+            Some(_) => checked_reply(display, || unsafe {
+                XShmGetImage(
+                    display,
+                    self.window.id,
+                    self.image.image,
+                    self.region.x,
+                    self.region.y,
+                    // The binding has the wrong type, in Xlib it's an `unsigned long`
+                    XAllPlanes() as u32,
+                )
+            })
+            .map(drop)?,
+
+            None => checked_reply(display, || unsafe {
+                XGetSubImage(
+                    display,
+                    self.window.id,
+                    self.region.x,
+                    self.region.y,
+                    self.region.width,
+                    self.region.height,
+                    XAllPlanes(),
+                    ZPixmap,
+                    self.image.image,
+                    0,
+                    0,
+                )
+            })
+            .map(drop)?,
+        }
+
+        Ok(&self.image)
+    }
+}
+
+impl Drop for ShmCapture<'_> {
+    fn drop(&mut self) {
+        if let Some(segment) = &mut self.segment {
+            unsafe {
+                // The detach would otherwise wait in Xlib's buffer until the next request,
+                // and the server has to be done with the segment before we let go of it
+                XShmDetach(self.display.display, &mut **segment);
+                XSync(self.display.display, false.into());
+
+                // The data is the shared memory, which is not Xlib's to free, the image
+                // itself still is, and `Image` frees it when it's dropped after this
+                (*self.image.image).data = null_mut();
+                libc::shmdt(segment.shmaddr as *const _);
+            }
+        }
+    }
+}
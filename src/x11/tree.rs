@@ -0,0 +1,146 @@
+use super::{
+    display::Display,
+    errors::{checked_reply, ProtocolError},
+    window::{MapState, Window},
+};
+use std::ffi::c_void;
+use x11::xlib::{XFree, XQueryTree};
+
+/// The `WM_STATE` value ICCCM window managers set on minimized windows
+const ICONIC_STATE: u32 = 3;
+
+struct Tree {
+    root: u64,
+    parent: u64,
+    children: Vec<u64>,
+}
+
+impl Window {
+    fn query_tree(&self) -> Result<Tree, ProtocolError> {
+        let (mut root, mut parent) = (0, 0);
+        let mut children_p = std::ptr::null_mut();
+        let mut children_count = 0;
+
+        checked_reply(self.display, || unsafe {
+            XQueryTree(
+                self.display,
+                self.id,
+                &mut root,
+                &mut parent,
+                &mut children_p,
+                &mut children_count,
+            )
+        })?;
+
+        let children = if children_p.is_null() {
+            Vec::new()
+        } else {
+            unsafe {
+                let children =
+                    std::slice::from_raw_parts(children_p, children_count as usize).to_vec();
+                XFree(children_p as *mut c_void);
+                children
+            }
+        };
+
+        Ok(Tree {
+            root,
+            parent,
+            children,
+        })
+    }
+
+    /// The windows directly inside of this one, in stacking order: the first one is at
+    /// the bottom, the last one is on top
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// let window = display.create_window().build().unwrap();
+    /// window.raise();
+    ///
+    /// // A new window that was just raised is at the top of the stack
+    /// assert!(root_window.children().unwrap().last() == Some(&window.window()));
+    /// ```
+    pub fn children(&self) -> Result<Vec<Window>, ProtocolError> {
+        Ok(self
+            .query_tree()?
+            .children
+            .into_iter()
+            .map(|id| Window {
+                id,
+                display: self.display,
+            })
+            .collect())
+    }
+
+    /// The window this window is inside of, `None` for root windows
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// let window = display.create_window().build().unwrap();
+    ///
+    /// assert!(window.parent().unwrap() == Some(root_window));
+    /// assert!(root_window.parent().unwrap().is_none());
+    /// ```
+    pub fn parent(&self) -> Result<Option<Window>, ProtocolError> {
+        Ok(Window::optional(self.display, self.query_tree()?.parent))
+    }
+
+    /// The root window of the screen this window is on
+    pub fn root(&self) -> Result<Window, ProtocolError> {
+        Ok(Window {
+            id: self.query_tree()?.root,
+            display: self.display,
+        })
+    }
+}
+
+impl Display {
+    /// Every top level window of every screen that a window manager should manage, in
+    /// stacking order (bottom first). These are the children of the root windows that are
+    /// not override-redirect (so no popups or menus) and that are either visible or
+    /// minimized, which is what a window manager has to adopt when it starts
+    ///
+    /// If another window manager is running and puts windows in frames, these will be
+    /// its frames
+    pub fn top_level_windows(&self) -> Result<Vec<Window>, ProtocolError> {
+        let wm_state = self.intern_atom("WM_STATE")?;
+        let mut windows = Vec::new();
+
+        for screen in self.screens() {
+            for window in screen.root_window.children()? {
+                // Windows can be destroyed at any moment, those are just skipped
+                let Ok(data) = window.try_get_data() else {
+                    continue;
+                };
+
+                if data.override_redirect {
+                    continue;
+                }
+
+                let is_iconic = || {
+                    window
+                        .get_property_of_type(wm_state, wm_state)
+                        .ok()
+                        .flatten()
+                        .and_then(|property| property.as_u32s()?.first().copied())
+                        == Some(ICONIC_STATE)
+                };
+
+                if data.map_state == MapState::Viewable || is_iconic() {
+                    windows.push(window);
+                }
+            }
+        }
+
+        Ok(windows)
+    }
+}
@@ -1,10 +1,16 @@
-use super::common::Vector2;
+use super::{
+    common::Vector2,
+    display::Display,
+    errors::{checked, ProtocolError},
+};
 use std::{
-    ffi::{c_char, CStr},
+    ffi::{c_char, c_int, CStr},
+    ops::{BitOr, BitOrAssign, Deref},
     ptr::null_mut,
 };
 use x11::xlib::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Modifier {
     Shift,
     Lock,
@@ -15,9 +21,133 @@ pub enum Modifier {
     ScrollLock,
 }
 
+/// A set of modifiers, they can be combined with `|`. A single `Modifier` can be used
+/// wherever a `Modifiers` is expected
+/// # Examples
+/// ```
+/// use undici::x11::window::{Modifier, Modifiers};
+///
+/// let modifiers = Modifiers::CONTROL | Modifier::Alt;
+///
+/// assert!(modifiers.contains(Modifiers::ALT));
+/// assert!(!modifiers.contains(Modifiers::SHIFT));
+/// assert_eq!(Modifiers::from(Modifier::Super), Modifiers::SUPER);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Modifiers(pub(crate) u16);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const SHIFT: Self = Self(1 << 0);
+
+    /// Caps Lock
+    pub const LOCK: Self = Self(1 << 1);
+    pub const CONTROL: Self = Self(1 << 2);
+    pub const ALT: Self = Self(1 << 3);
+
+    /// Num Lock, keyboards without a Num Lock key don't have this modifier, so grabs
+    /// leave it out and it's never reported in events
+    pub const NUM: Self = Self(1 << 4);
+    pub const SUPER: Self = Self(1 << 5);
+
+    /// Like `NUM`, this is left out on keyboards without a Scroll Lock key
+    pub const SCROLL_LOCK: Self = Self(1 << 6);
+
+    /// Only makes sense for grabs, it matches whatever modifiers are held, including none
+    pub const ANY: Self = Self(1 << 15);
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<Modifier> for Modifiers {
+    fn from(modifier: Modifier) -> Self {
+        match modifier {
+            Modifier::Shift => Modifiers::SHIFT,
+            Modifier::Lock => Modifiers::LOCK,
+            Modifier::Control => Modifiers::CONTROL,
+            Modifier::Alt => Modifiers::ALT,
+            Modifier::Num => Modifiers::NUM,
+            Modifier::Super => Modifiers::SUPER,
+            Modifier::ScrollLock => Modifiers::SCROLL_LOCK,
+        }
+    }
+}
+
+impl<T: Into<Modifiers>> BitOr<T> for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, other: T) -> Self {
+        Self(self.0 | other.into().0)
+    }
+}
+
+impl<T: Into<Modifiers>> BitOr<T> for Modifier {
+    type Output = Modifiers;
+
+    fn bitor(self, other: T) -> Modifiers {
+        Modifiers::from(self) | other
+    }
+}
+
+impl<T: Into<Modifiers>> BitOrAssign<T> for Modifiers {
+    fn bitor_assign(&mut self, other: T) {
+        self.0 |= other.into().0;
+    }
+}
+
+/// Where a window goes in the stack, relative to a sibling if there is one, otherwise
+/// relative to all of its siblings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackMode {
+    Above,
+    Below,
+
+    /// Raise the window only if the sibling is covering it
+    TopIf,
+
+    /// Lower the window only if it is covering the sibling
+    BottomIf,
+
+    /// `TopIf` or `BottomIf`, whichever applies
+    Opposite,
+}
+
+/// Changes to a window's geometry and stacking, the fields that are `None` are left as
+/// they are
+#[derive(Default, PartialEq, Clone, Copy)]
+pub struct WindowChanges {
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub border_width: Option<u32>,
+    pub sibling: Option<Window>,
+    pub stack_mode: Option<StackMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapState {
+    Unmapped,
+
+    /// The window is mapped, but one of its ancestors is not, so it still can't be seen
+    Unviewable,
+    Viewable,
+}
+
 pub struct WindowData {
     pub position: Vector2<i32>,
     pub scale: Vector2<i32>,
+    pub border_width: i32,
+    pub map_state: MapState,
+
+    /// If window managers should leave this window alone, popups and menus do this
+    pub override_redirect: bool,
 }
 
 /// We need a default window attributes const because the XGetWindowAttributes requires a
@@ -53,6 +183,68 @@ pub fn kill_window(window: &mut Window) {
     unsafe { XDestroyWindow(window.display, window.id) };
 }
 
+/// Like `kill_window`, but reports if the window was already gone
+pub fn try_kill_window(window: &mut Window) -> Result<(), ProtocolError> {
+    checked(window.display, || unsafe {
+        XDestroyWindow(window.display, window.id)
+    })?;
+    Ok(())
+}
+
+/// A window that we created ourselves, see `Display::create_window()`. Unlike a normal
+/// `Window`, it gets destroyed when it goes out of scope. It derefs to `Window`, so all
+/// the usual methods work on it. It borrows the `Display` it was created on, so the
+/// display can't be closed while the window is still around
+/// ```compile_fail
+/// use undici::x11::display::Display;
+///
+/// let display = Display::new().expect("could not open display");
+/// let window = display.create_window().build().expect("could not create window");
+///
+/// drop(display);
+/// window.map();
+/// ```
+pub struct OwnedWindow<'a> {
+    pub(crate) window: Window,
+    pub(crate) display: &'a Display,
+
+    /// Windows with a non default visual need their own colormap, which we have to free
+    pub(crate) colormap: Option<Colormap>,
+}
+
+impl OwnedWindow<'_> {
+    /// A copy of the underlying `Window`, keep in mind that it stops being valid once the
+    /// `OwnedWindow` is dropped
+    pub fn window(&self) -> Window {
+        self.window
+    }
+}
+
+impl Deref for OwnedWindow<'_> {
+    type Target = Window;
+
+    fn deref(&self) -> &Window {
+        &self.window
+    }
+}
+
+impl Drop for OwnedWindow<'_> {
+    fn drop(&mut self) {
+        let display = self.display.display;
+
+        unsafe {
+            XDestroyWindow(display, self.window.id);
+
+            if let Some(colormap) = self.colormap {
+                XFreeColormap(display, colormap);
+            }
+
+            // Otherwise the window would stay on screen until the next request gets sent
+            XFlush(display);
+        }
+    }
+}
+
 /// # Warning
 /// If this is not used in a loop (for example, a wm), you need to manually kill the
 /// window with the `kill_window(&mut window)` function
@@ -65,6 +257,11 @@ pub struct Window {
 }
 
 impl Window {
+    /// X uses the id `0` (`None`) when there is no window
+    pub(crate) fn optional(display: *mut _XDisplay, id: u64) -> Option<Window> {
+        (id != 0).then_some(Window { id, display })
+    }
+
     /// # Examples
     /// ```
     /// use undici::x11::display::Display;
@@ -122,13 +319,203 @@ impl Window {
     pub fn lower(&self) {
         unsafe { XLowerWindow(self.display, self.id) };
     }
+
+    /// Shows the window, windows are not visible until they are mapped. If a window
+    /// manager is running, this will ask it to show the window instead
+    pub fn map(&self) {
+        unsafe { XMapWindow(self.display, self.id) };
+    }
+
+    /// Like `map()`, but also puts the window on top of all other windows
+    pub fn map_raised(&self) {
+        unsafe { XMapRaised(self.display, self.id) };
+    }
+
+    /// Hides the window, the opposite of `map()`
+    pub fn unmap(&self) {
+        unsafe { XUnmapWindow(self.display, self.id) };
+    }
+
+    /// Moves the window inside of `parent`, at `position` relative to it. This is how
+    /// window managers put decorations around windows
+    pub fn reparent(&self, parent: Window, position: Vector2<i32>) {
+        unsafe { XReparentWindow(self.display, self.id, parent.id, position.x, position.y) };
+    }
+
+    /// Moves, resizes and restacks the window in a single request, only the fields of
+    /// `changes` that are `Some` are changed
+    /// # Examples
+    /// ```
+    /// use undici::x11::{display::Display, window::{StackMode, WindowChanges}};
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let window = display.create_window().build().expect("could not create window");
+    ///
+    /// window.configure(&WindowChanges {
+    ///     width: Some(640),
+    ///     height: Some(480),
+    ///     stack_mode: Some(StackMode::Above),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert_eq!(window.get_data().scale.x, 640);
+    /// ```
+    pub fn configure(&self, changes: &WindowChanges) {
+        let mut value_mask = 0;
+        let mut xchanges: XWindowChanges = unsafe { std::mem::zeroed() };
+
+        if let Some(x) = changes.x {
+            xchanges.x = x;
+            value_mask |= CWX;
+        }
+
+        if let Some(y) = changes.y {
+            xchanges.y = y;
+            value_mask |= CWY;
+        }
+
+        if let Some(width) = changes.width {
+            xchanges.width = width as c_int;
+            value_mask |= CWWidth;
+        }
+
+        if let Some(height) = changes.height {
+            xchanges.height = height as c_int;
+            value_mask |= CWHeight;
+        }
+
+        if let Some(border_width) = changes.border_width {
+            xchanges.border_width = border_width as c_int;
+            value_mask |= CWBorderWidth;
+        }
+
+        if let Some(sibling) = changes.sibling {
+            xchanges.sibling = sibling.id;
+            value_mask |= CWSibling;
+        }
+
+        if let Some(stack_mode) = changes.stack_mode {
+            xchanges.stack_mode = stack_mode.into();
+            value_mask |= CWStackMode;
+        }
+
+        unsafe { XConfigureWindow(self.display, self.id, value_mask.into(), &mut xchanges) };
+    }
+}
+
+/// These are the same as the methods without the `try_` prefix, but they wait for the X
+/// server to answer and report the error if there was one. This is what you want when
+/// the window might have been destroyed in the meantime (a window manager managing
+/// someone else's windows for example), but it costs a round trip to the server
+impl Window {
+    /// # Examples
+    /// ```
+    /// use undici::x11::display::Display;
+    ///
+    /// let display = Display::new().expect("could not open display");
+    /// let root_window = display.get_root_window();
+    ///
+    /// match root_window.try_get_data() {
+    ///     Ok(data) => println!("Root window is {}x{}", data.scale.x, data.scale.y),
+    ///     Err(error) => println!("The root window is gone?! {}", error),
+    /// }
+    /// ```
+    pub fn try_get_data(&self) -> Result<WindowData, ProtocolError> {
+        Ok(self.try_get_attributes()?.into())
+    }
+
+    pub(crate) fn try_get_attributes(&self) -> Result<XWindowAttributes, ProtocolError> {
+        let mut attributes = NEW_WINDOW_ATTRIBUTES;
+        checked(self.display, || unsafe {
+            XGetWindowAttributes(self.display, self.id, &mut attributes)
+        })?;
+
+        Ok(attributes)
+    }
+
+    pub fn try_get_name(&self) -> Result<Option<String>, ProtocolError> {
+        checked(self.display, || self.get_name())
+    }
+
+    pub fn try_set_position(&self, position: Vector2<i32>) -> Result<(), ProtocolError> {
+        checked(self.display, || self.set_position(position))
+    }
+
+    pub fn try_set_scale(&self, scale: Vector2<u32>) -> Result<(), ProtocolError> {
+        checked(self.display, || self.set_scale(scale))
+    }
+
+    pub fn try_raise(&self) -> Result<(), ProtocolError> {
+        checked(self.display, || self.raise())
+    }
+
+    pub fn try_lower(&self) -> Result<(), ProtocolError> {
+        checked(self.display, || self.lower())
+    }
+
+    pub fn try_configure(&self, changes: &WindowChanges) -> Result<(), ProtocolError> {
+        checked(self.display, || self.configure(changes))
+    }
+
+    pub fn try_map(&self) -> Result<(), ProtocolError> {
+        checked(self.display, || self.map())
+    }
+
+    pub fn try_map_raised(&self) -> Result<(), ProtocolError> {
+        checked(self.display, || self.map_raised())
+    }
+
+    pub fn try_unmap(&self) -> Result<(), ProtocolError> {
+        checked(self.display, || self.unmap())
+    }
+
+    pub fn try_reparent(
+        &self,
+        parent: Window,
+        position: Vector2<i32>,
+    ) -> Result<(), ProtocolError> {
+        checked(self.display, || self.reparent(parent, position))
+    }
+}
+
+impl From<c_int> for StackMode {
+    #[allow(non_upper_case_globals)]
+    fn from(stack_mode: c_int) -> Self {
+        match stack_mode {
+            Below => StackMode::Below,
+            TopIf => StackMode::TopIf,
+            BottomIf => StackMode::BottomIf,
+            Opposite => StackMode::Opposite,
+            _ => StackMode::Above,
+        }
+    }
+}
+
+impl From<StackMode> for c_int {
+    fn from(stack_mode: StackMode) -> Self {
+        match stack_mode {
+            StackMode::Above => Above,
+            StackMode::Below => Below,
+            StackMode::TopIf => TopIf,
+            StackMode::BottomIf => BottomIf,
+            StackMode::Opposite => Opposite,
+        }
+    }
 }
 
 impl From<XWindowAttributes> for WindowData {
+    #[allow(non_upper_case_globals)]
     fn from(attributes: XWindowAttributes) -> Self {
         WindowData {
             position: Vector2::new(attributes.x, attributes.y),
             scale: Vector2::new(attributes.width, attributes.height),
+            border_width: attributes.border_width,
+            map_state: match attributes.map_state {
+                IsViewable => MapState::Viewable,
+                IsUnviewable => MapState::Unviewable,
+                _ => MapState::Unmapped,
+            },
+            override_redirect: attributes.override_redirect != 0,
         }
     }
 }